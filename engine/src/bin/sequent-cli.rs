@@ -0,0 +1,212 @@
+// Command line tool to check level files without opening the game, for example in CI.
+
+use std::process::ExitCode;
+
+use sequent_engine::proof::*;
+use sequent_engine::parser::*;
+
+pub const LEVELS_PATH: &str = "assets/levels";
+
+const USAGE: &str = "Usage:
+    sequent-cli check <file>...           Parse level files and report the errors
+    sequent-cli prove <sequent>           Tell if the sequent is provable, or give a counter-model
+    sequent-cli solve [--ascii | --latex | --ebproofs] <sequent>
+                                          Print a natural deduction proof of the sequent, as a tree or in LaTeX
+    sequent-cli lint [<file>...]          Report false, unprovable and duplicated levels, and wrong RAA flags.
+                                          Checks all the files of assets/levels by default.
+    sequent-cli convert <file>            Print a level file in the structured .ron format
+
+Sequents are written like in level files, for example \"A -> B, A |- B\".
+The exit code is 1 if a file has errors, or if the sequent can't be proven.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match args.first().map(|a| a.as_str()) {
+        Some("check") if args.len() > 1 => check(&args[1..]),
+        Some("prove") if args.len() == 2 => prove(&args[1]),
+        Some("solve") if args.len() == 2 => solve(&args[1], ProofFormat::Text(text::Symbols::Unicode)),
+        Some("solve") if args.len() == 3 && args[1] == "--ascii" => solve(&args[2], ProofFormat::Text(text::Symbols::Ascii)),
+        Some("solve") if args.len() == 3 && args[1] == "--latex" => solve(&args[2], ProofFormat::Latex(latex::LatexStyle::Bussproofs)),
+        Some("solve") if args.len() == 3 && args[1] == "--ebproofs" => solve(&args[2], ProofFormat::Latex(latex::LatexStyle::Ebproofs)),
+        Some("lint") => lint(&args[1..]),
+        Some("convert") if args.len() == 2 => convert(&args[1]),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        },
+    };
+
+    return if result { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+}
+
+fn read_sequent(text: &str) -> Option<Sequent> {
+    match parse_sequent(text, "<sequent>", 1, 1) {
+        Ok(s) => Some(s),
+        Err(e) => {
+            println!("{}", e);
+            None
+        },
+    }
+}
+
+/// Returns false if a file couldn't be read or has a level with a syntax error
+fn check(files: &[String]) -> bool {
+    let mut ok = true;
+
+    for file in files {
+        match parse_file(file) {
+            Ok(campaign) => {
+                for e in campaign.errors.iter() {
+                    println!("{}", e);
+                }
+                println!("{}: {} levels, {} errors", file, campaign.levels.len(), campaign.errors.len());
+                ok &= campaign.errors.is_empty();
+            },
+            Err(e) => {
+                println!("{}", e.message);
+                ok = false;
+            },
+        }
+    }
+
+    return ok;
+}
+
+/// Returns false if the sequent can't be proven
+fn prove(text: &str) -> bool {
+    let Some(s) = read_sequent(text) else { return false };
+
+    match calcul::proof_or_fake(s.clone()) {
+        Some(calcul::CalcResult::Provable(_)) => {
+            match intuitionistic::is_provable(&s) {
+                Some(false) => println!("Provable, only with RAA"),
+                _ => println!("Provable without RAA"),
+            }
+            return true;
+        },
+        Some(calcul::CalcResult::CounterModel(valuation)) => {
+            let mut variables: Vec<&Variable> = valuation.keys().collect();
+            variables.sort();
+
+            let values: Vec<String> = variables.iter().map(|v| {
+                format!("{} = {}",
+                    variable_name(&s.variables, **v),
+                    get_operator_symbol(if valuation[v] { OperatorType::Top } else { OperatorType::Bottom })
+                )
+            }).collect();
+
+            println!("Not provable: the sequent is false when {}", values.join(", "));
+            return false;
+        },
+        None => {
+            println!("The sequent isn't complete");
+            return false;
+        },
+    }
+}
+
+enum ProofFormat {
+    Text(text::Symbols),
+    Latex(latex::LatexStyle),
+}
+
+/// Prints the proof found by the automatic prover. Returns false if there is none.
+fn solve(sequent: &str, format: ProofFormat) -> bool {
+    let Some(s) = read_sequent(sequent) else { return false };
+
+    let Some(p) = prover::find_proof(&s, 0.0, &mut 0) else {
+        println!("No proof found");
+        return false;
+    };
+
+    let system = natural_logic::get_system();
+    match format {
+        ProofFormat::Text(symbols) => print!("{}", text::proof_tree_text(&p, &system, symbols)),
+        ProofFormat::Latex(style) => print!("{}", latex::proof_to_latex(&p, &system, style)),
+    }
+
+    return true;
+}
+
+/// Returns false if a level has a problem
+fn lint(files: &[String]) -> bool {
+    let files: Vec<String> = if files.is_empty() {
+        match std::fs::read_dir(LEVELS_PATH) {
+            Ok(entries) => {
+                let mut files: Vec<String> = entries.filter_map(|f| f.ok()).map(|f| f.path().to_string_lossy().to_string()).collect();
+                files.sort();
+                files
+            },
+            Err(e) => {
+                println!("Couldn't read the levels folder {}: {}", LEVELS_PATH, e);
+                return false;
+            },
+        }
+    } else {
+        files.to_vec()
+    };
+
+    let mut ok = true;
+    // First level with each sequent, to find the same level in several campaigns
+    let mut sequents: Vec<(Sequent, String, usize)> = Vec::new();
+
+    for file in files {
+        let mut campaign = match parse_file(&file) {
+            Ok(campaign) => campaign,
+            Err(e) => {
+                println!("{}", e.message);
+                ok = false;
+                continue;
+            },
+        };
+
+        for e in campaign.errors.iter() {
+            println!("Level skipped: {}", e);
+            ok = false;
+        }
+
+        for warning in check_campaign(&mut campaign) {
+            println!("{}", warning);
+            ok = false;
+        }
+
+        for level in campaign.levels.iter() {
+            let previous = sequents.iter().find(|(s, _, _)| s.before == level.seq.before && s.after == level.seq.after);
+            match previous {
+                // Duplicates in the same campaign are reported by check_campaign
+                Some((_, other_campaign, _)) if *other_campaign == campaign.name => { },
+                Some((_, other_campaign, other_id)) => {
+                    println!("Level {} of {} has the same sequent as level {} of {}", level.id, campaign.name, other_id, other_campaign);
+                    ok = false;
+                },
+                None => sequents.push((level.seq.clone(), campaign.name.clone(), level.id)),
+            }
+        }
+    }
+
+    if ok {
+        println!("No problem found");
+    }
+
+    return ok;
+}
+
+/// Returns false if the file couldn't be read, or has levels with errors that are left out
+fn convert(file: &str) -> bool {
+    match parse_file(file) {
+        Ok(campaign) => {
+            // Errors go to stderr, so that the output can be written to a file
+            for e in campaign.errors.iter() {
+                eprintln!("Level skipped: {}", e);
+            }
+            println!("{}", campaign_to_ron(&campaign));
+
+            return campaign.errors.is_empty();
+        },
+        Err(e) => {
+            eprintln!("{}", e.message);
+            return false;
+        },
+    }
+}
@@ -0,0 +1,42 @@
+// Dates as YYYY-MM-DD, computed without a dependency.
+// The puzzle of the day and the progress of the player are indexed by them.
+
+/// Today's date (UTC), as YYYY-MM-DD
+pub fn current_date() -> String {
+    let seconds = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (year, month, day) = date_from_days((seconds / 86400) as i64);
+
+    return format!("{:04}-{:02}-{:02}", year, month, day);
+}
+
+/// Converts a number of days since 1970-01-01 to (year, month, day)
+pub fn date_from_days(days: i64) -> (i64, u32, u32) {
+    // Days since 0000-03-01, so that leap days are at the end of the years
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153; // 0 is March
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    return (year, month, day);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates() {
+        assert_eq!(date_from_days(0), (1970, 1, 1));
+        assert_eq!(date_from_days(59), (1970, 3, 1));
+        assert_eq!(date_from_days(11016), (2000, 2, 29));
+        assert_eq!(date_from_days(19782), (2024, 2, 29));
+        assert_eq!(date_from_days(20744), (2026, 10, 18));
+        assert_eq!(date_from_days(-1), (1969, 12, 31));
+    }
+}
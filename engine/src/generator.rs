@@ -0,0 +1,285 @@
+// Random sequents, for endless practice.
+// A challenge is generated from a seed and a difficulty, so that the same puzzle can be shared.
+
+use crate::parser::Difficulty;
+use crate::proof::{Formula, Operator, OperatorType, Sequent, count_rules, intuitionistic, prover};
+
+
+pub const DIFFICULTIES: [Difficulty; 4] = [Difficulty::Immediate, Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+/// Difficulty of the puzzle of the day
+pub const DAILY_DIFFICULTY: Difficulty = Difficulty::Medium;
+
+/// Maximum number of sequents generated before giving up on finding one of the requested difficulty
+const MAX_ATTEMPTS: u32 = 300;
+
+/// Small xorshift generator. Challenges are shared by their seed, so the numbers must be the same on every machine.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // Splitmix, so that close seeds give different sequences, and the state is never 0
+        let mut z = seed.wrapping_add(0x9e3779b97f4a7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        return Rng((z ^ (z >> 31)) | 1);
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        return self.0;
+    }
+
+    pub fn below(&mut self, n: u32) -> u32 {
+        return (self.next_u64() % n as u64) as u32;
+    }
+}
+
+/// What the generated sequents are made of
+#[derive(Clone, Debug)]
+pub struct GeneratorSettings {
+    pub variable_count: u32,
+    /// Maximum number of nested operators in a formula
+    pub depth: u32,
+    pub max_hypotheses: u32,
+    /// Relative frequency of variables among the nodes of the formulas
+    pub variable_weight: u32,
+    /// Relative frequency of each operator. Operators of arity 0 (⊤, ⊥) are leaves, like variables.
+    pub operators: Vec<(OperatorType, u32)>,
+}
+
+impl GeneratorSettings {
+    pub fn for_difficulty(difficulty: &Difficulty) -> GeneratorSettings {
+        let (variable_count, depth, max_hypotheses) = match difficulty {
+            Difficulty::Immediate => (2, 1, 2),
+            Difficulty::Easy => (2, 2, 2),
+            Difficulty::Medium => (3, 2, 2),
+            Difficulty::Hard => (3, 3, 2),
+        };
+
+        return GeneratorSettings {
+            variable_count,
+            depth,
+            max_hypotheses,
+            variable_weight: 4,
+            operators: vec![
+                (OperatorType::Not, 2),
+                (OperatorType::Impl, 3),
+                (OperatorType::And, 2),
+                (OperatorType::Or, 2),
+                (OperatorType::Top, 0),
+                (OperatorType::Bottom, 1),
+            ],
+        };
+    }
+}
+
+pub fn random_formula(rng: &mut Rng, settings: &GeneratorSettings, depth: u32) -> Formula {
+    // Operators that can be used at this depth
+    let operators: Vec<(OperatorType, u32)> = settings.operators.iter()
+        .filter(|(op, _)| depth > 0 || *op == OperatorType::Top || *op == OperatorType::Bottom)
+        .copied()
+        .collect();
+
+    let total_weight = settings.variable_weight + operators.iter().map(|(_, w)| w).sum::<u32>();
+    let mut pick = rng.below(u32::max(total_weight, 1));
+
+    for (operator_type, weight) in operators {
+        if pick >= weight {
+            pick -= weight;
+            continue;
+        }
+
+        let mut arg = || Some(Box::new(random_formula(rng, settings, depth - 1)));
+        let (arg1, arg2) = match operator_type {
+            OperatorType::Top | OperatorType::Bottom => (None, None),
+            OperatorType::Not => (arg(), None),
+            _ => (arg(), arg()),
+        };
+
+        return Formula::Operator(Operator { operator_type, arg1, arg2 });
+    }
+
+    return Formula::Variable(rng.below(settings.variable_count));
+}
+
+/// Random sequent, that may not be provable
+pub fn random_sequent(rng: &mut Rng, settings: &GeneratorSettings) -> Sequent {
+    let hypothesis_count = rng.below(settings.max_hypotheses + 1);
+    let before: Vec<Formula> = (0..hypothesis_count).map(|_| random_formula(rng, settings, settings.depth)).collect();
+    let after = random_formula(rng, settings, settings.depth);
+
+    let mut s = Sequent { before, after: vec![after], variables: Vec::new() };
+    number_variables(&mut s);
+    return s;
+}
+
+/// Renames the variables in order of appearance, so that a sequent with only B and C uses A and B
+fn number_variables(s: &mut Sequent) {
+    fn rename(f: &mut Formula, ids: &mut Vec<u32>) {
+        match f {
+            Formula::Variable(id) => {
+                let new_id = match ids.iter().position(|i| i == id) {
+                    Some(new_id) => new_id,
+                    None => {
+                        ids.push(*id);
+                        ids.len() - 1
+                    },
+                };
+                *id = new_id as u32;
+            },
+            Formula::Operator(operator) => {
+                for arg in operator.arg1.iter_mut().chain(operator.arg2.iter_mut()) {
+                    rename(arg, ids);
+                }
+            },
+            Formula::NotCompleted(_) => {},
+        }
+    }
+
+    let mut ids = Vec::new();
+    for f in s.before.iter_mut().chain(s.after.iter_mut()) {
+        rename(f, &mut ids);
+    }
+}
+
+/// How hard a provable sequent is
+#[derive(Clone, Debug, PartialEq)]
+pub struct Estimate {
+    /// Number of rules in the shortest proof found by the prover
+    pub rule_count: u32,
+    /// The sequent can't be proven without RAA
+    pub needs_raa: bool,
+    pub difficulty: Difficulty,
+}
+
+/// Returns None if the sequent can't be proven
+pub fn estimate_difficulty(s: &Sequent) -> Option<Estimate> {
+    let proof = prover::find_shortest_proof(s, 0.0, &mut 0)?;
+    let rule_count = count_rules(&proof);
+    let needs_raa = intuitionistic::is_provable(s) == Some(false);
+
+    // Calibrated on the levels of the game
+    let level = match rule_count {
+        0..=4 => 0,
+        5..=10 => 1,
+        11..=24 => 2,
+        _ => 3,
+    };
+
+    // RAA proofs need an idea that isn't given by the shape of the sequent
+    let level = usize::min(level + needs_raa as usize, DIFFICULTIES.len() - 1);
+    let difficulty = DIFFICULTIES[level].clone();
+
+    return Some(Estimate { rule_count, needs_raa, difficulty });
+}
+
+/// A generated level
+#[derive(Clone, Debug)]
+pub struct Challenge {
+    pub seed: u32,
+    /// Difficulty asked for. The estimate can differ if no sequent of this difficulty was found.
+    pub difficulty: Difficulty,
+    pub seq: Sequent,
+    pub estimate: Estimate,
+}
+
+/// Provable sequent of the difficulty, or the closest one found. Always gives the same sequent for the same seed and difficulty.
+pub fn random_challenge(seed: u32, difficulty: &Difficulty) -> Option<Challenge> {
+    let mut rng = Rng::new(seed as u64);
+    let settings = GeneratorSettings::for_difficulty(difficulty);
+    let distance = |estimate: &Estimate| (estimate.difficulty.clone() as i32 - difficulty.clone() as i32).abs();
+
+    let mut closest: Option<Challenge> = None;
+
+    for _ in 0..MAX_ATTEMPTS {
+        let seq = random_sequent(&mut rng, &settings);
+        let Some(estimate) = estimate_difficulty(&seq) else { continue };
+
+        if estimate.difficulty == *difficulty {
+            return Some(Challenge { seed, difficulty: difficulty.clone(), seq, estimate });
+        }
+
+        if closest.as_ref().is_none_or(|c| distance(&estimate) < distance(&c.estimate)) {
+            closest = Some(Challenge { seed, difficulty: difficulty.clone(), seq, estimate });
+        }
+    }
+
+    return closest;
+}
+
+/// Seed of the puzzle of the day, from a date as YYYY-MM-DD. Everyone gets the same puzzle on the same day, without a server.
+pub fn daily_seed(date: &str) -> u32 {
+    // FNV-1a, which doesn't depend on the platform or on the version of Rust, unlike the hashers of std
+    let mut hash: u32 = 0x811c9dc5;
+    for byte in date.bytes() {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x01000193);
+    }
+
+    return hash;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_sequent;
+
+    fn sequent(text: &str) -> Sequent {
+        parse_sequent(text, "test.sq", 1, 1).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    #[test]
+    fn estimates() {
+        let estimate = |text: &str| estimate_difficulty(&sequent(text));
+
+        assert_eq!(estimate("A - B"), None);
+        assert_eq!(estimate("A - A").map(|e| e.difficulty), Some(Difficulty::Immediate));
+        assert_eq!(estimate("A > B, A - B").map(|e| e.rule_count), Some(3));
+        assert_eq!(estimate("- A | !A").map(|e| (e.needs_raa, e.difficulty)), Some((true, Difficulty::Hard)));
+        assert!(!estimate("A | (B & C) - (A | B) & (A | C)").unwrap().needs_raa);
+    }
+
+    #[test]
+    fn challenges() {
+        for difficulty in DIFFICULTIES {
+            for seed in 0..5 {
+                let challenge = random_challenge(seed, &difficulty).unwrap();
+
+                assert!(estimate_difficulty(&challenge.seq).is_some());
+                assert_eq!(challenge.estimate.difficulty, difficulty, "{}", challenge.seq);
+
+                // Same seed, same puzzle
+                assert_eq!(random_challenge(seed, &difficulty).unwrap().seq, challenge.seq);
+            }
+        }
+
+        assert_ne!(random_challenge(0, &Difficulty::Medium).unwrap().seq, random_challenge(1, &Difficulty::Medium).unwrap().seq);
+    }
+
+    #[test]
+    fn closest_challenge() {
+        // No medium sequent is found for this seed, the closest one is kept with its own difficulty
+        let challenge = random_challenge(28, &Difficulty::Medium).unwrap();
+        assert_eq!(challenge.difficulty, Difficulty::Medium);
+        assert_eq!(challenge.estimate.difficulty, Difficulty::Easy);
+        assert_eq!(Some(challenge.estimate), estimate_difficulty(&challenge.seq));
+    }
+
+    #[test]
+    fn daily_challenges() {
+        // Changing these values changes the puzzle of every day
+        assert_eq!(daily_seed(""), 0x811c9dc5);
+        assert_eq!(daily_seed("2026-10-18"), 0x527f502b);
+        assert_ne!(daily_seed("2026-10-19"), daily_seed("2026-10-18"));
+
+        for day in 0..5 {
+            let date = format!("2026-10-{:02}", 18 + day);
+            let challenge = random_challenge(daily_seed(&date), &DAILY_DIFFICULTY).unwrap();
+            assert_eq!(challenge.estimate.difficulty, DAILY_DIFFICULTY, "{}", date);
+        }
+    }
+}
@@ -0,0 +1,9 @@
+// Proof engine of the game: formulas, sequents, natural deduction rules, level files, decision procedures and random levels.
+// Doesn't depend on notan, so that other tools can use it and it can be tested without a display.
+
+pub mod proof;
+pub mod parser;
+pub mod generator;
+pub mod date;
+
+pub use proof::*;
@@ -0,0 +1,928 @@
+use crate::Sequent;
+use crate::Formula;
+use crate::Operator;
+use crate::OperatorType;
+use crate::MAX_VARIABLE_COUNT;
+use crate::get_operator_priority;
+use crate::proof::text::{Symbols, sequent_text};
+
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Difficulty {
+    Immediate,
+    Easy,
+    Medium,
+    Hard,
+}
+
+#[derive(Clone)]
+pub struct Campaign {
+    /// Declared in the structured format, the file name in the legacy one. Progress, saves and unlocks refer to campaigns by it.
+    pub name: String,
+    /// Campaigns are listed by increasing order, then by name
+    pub order: i32,
+    pub unlock: Unlock,
+    pub levels: Vec<Level>,
+    /// Levels that couldn't be read, and were skipped
+    pub errors: Vec<ParseError>,
+}
+
+/// What the player must do before playing a campaign. Nothing by default.
+#[derive(Clone, Default, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Unlock {
+    /// Campaigns, by name, whose levels must all be solved
+    pub campaigns: Vec<String>,
+    /// Number of levels to solve, in any campaign
+    pub solved_levels: usize,
+}
+
+pub use crate::proof::RuleFilter;
+
+#[derive(Clone)]
+pub struct Level {
+    pub id: usize,
+    pub name: String,
+    pub description: String,
+    pub seq: Sequent,
+    pub difficulty: Difficulty,
+    pub raa: bool,
+    pub rules: RuleFilter,
+    pub hints: Vec<String>,
+    /// Time and number of rules to beat, set by the author
+    pub par_time: Option<f32>,
+    pub par_steps: Option<u32>,
+    pub author: Option<String>,
+    /// Set when loading the campaign: true if the level can't be solved without RAA
+    pub classical_only: bool,
+}
+
+impl Level {
+    pub fn empty() -> Level{
+        let seq = Sequent {before: vec![], after: vec![], variables: vec![]};
+        Level {
+            id: 0, name: "".to_string(), description: "".to_string(), seq, difficulty: Difficulty::Immediate, raa: false,
+            rules: RuleFilter::default(), hints: vec![], par_time: None, par_steps: None, author: None, classical_only: false,
+        }
+    }
+}
+
+/// Syntax error in a level file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: String,
+    /// Id of the level and name of the field, in the structured format. The line and column are then the ones in the string of the field.
+    pub field: Option<(usize, String)>,
+    /// Starts at 1
+    pub line: usize,
+    /// In characters, starts at 1
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.field {
+            Some((level_id, field)) => write!(f, "{}: level {}, {}, column {}: expected {}, found {}", self.file, level_id, field, self.column, self.expected, self.found),
+            None => write!(f, "{}:{}:{}: expected {}, found {}", self.file, self.line, self.column, self.expected, self.found),
+        }
+    }
+}
+
+/// Error while opening a level file
+pub struct LoadError {
+    pub message: String,
+}
+
+/// Reads a part of a file, ignoring spaces, and keeps track of the position for error messages
+struct Cursor<'a> {
+    chars: Vec<char>,
+    /// Line and column in the file of each char, then of the end of the text
+    positions: Vec<(usize, usize)>,
+    pos: usize,
+    file: &'a str,
+}
+
+impl<'a> Cursor<'a> {
+    /// Text written on a single line, starting at first_column
+    fn new(text: &str, file: &'a str, line: usize, first_column: usize) -> Cursor<'a> {
+        let chars: Vec<char> = text.chars().collect();
+        let positions = (0..=chars.len()).map(|i| (line, first_column + i)).collect();
+
+        Cursor { chars, positions, pos: 0, file }
+    }
+
+    /// The $ of LaTeX math mode are ignored like spaces
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace() || *c == '$') {
+            self.pos += 1;
+        }
+
+        return self.chars.get(self.pos).copied();
+    }
+
+    /// Next token and its length in chars. Takes the longest spelling that matches, so "->" isn't read as "-".
+    fn peek_token(&mut self) -> Option<(Token, usize)> {
+        let first = self.peek()?;
+        let rest = &self.chars[self.pos..];
+
+        let spelling = SPELLINGS.iter()
+            .filter(|(text, _)| spelled_at(rest, text))
+            .max_by_key(|(text, _)| text.chars().count());
+
+        match spelling {
+            Some((text, token)) => Some((token.clone(), text.chars().count())),
+            None if first.is_ascii_alphabetic() => {
+                let name: String = rest.iter().take_while(|c| is_name_char(**c)).collect();
+                let len = name.len();
+                Some((Token::Variable(name), len))
+            },
+            None => None,
+        }
+    }
+
+    fn next_token(&mut self) -> Option<Token> {
+        let (token, len) = self.peek_token()?;
+        self.pos += len;
+
+        return Some(token);
+    }
+
+    /// Error at the next char
+    fn error(&mut self, expected: &str) -> ParseError {
+        let found = match self.peek() {
+            Some(c) => format!("'{}'", c),
+            None => String::from("nothing"),
+        };
+
+        let (line, column) = self.positions[self.pos];
+
+        return ParseError {
+            file: self.file.to_string(),
+            field: None,
+            line,
+            column,
+            expected: expected.to_string(),
+            found,
+        };
+    }
+
+}
+
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Operator(OperatorType),
+    Turnstile,
+    Comma,
+    Open,
+    Close,
+    /// A letter followed by letters and digits, like P1 or rain
+    Variable(String),
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric()
+}
+
+/// Every accepted spelling of the symbols: the original level syntax, the symbols shown in game, ASCII and LaTeX
+const SPELLINGS: [(&str, Token); 33] = [
+    ("!", Token::Operator(OperatorType::Not)),
+    ("¬", Token::Operator(OperatorType::Not)),
+    ("~", Token::Operator(OperatorType::Not)),
+    ("\\lnot", Token::Operator(OperatorType::Not)),
+    ("\\neg", Token::Operator(OperatorType::Not)),
+    (">", Token::Operator(OperatorType::Impl)),
+    ("→", Token::Operator(OperatorType::Impl)),
+    ("->", Token::Operator(OperatorType::Impl)),
+    ("\\to", Token::Operator(OperatorType::Impl)),
+    ("\\rightarrow", Token::Operator(OperatorType::Impl)),
+    ("&", Token::Operator(OperatorType::And)),
+    ("∧", Token::Operator(OperatorType::And)),
+    ("/\\", Token::Operator(OperatorType::And)),
+    ("\\land", Token::Operator(OperatorType::And)),
+    ("\\wedge", Token::Operator(OperatorType::And)),
+    ("|", Token::Operator(OperatorType::Or)),
+    ("∨", Token::Operator(OperatorType::Or)),
+    ("\\/", Token::Operator(OperatorType::Or)),
+    ("\\lor", Token::Operator(OperatorType::Or)),
+    ("\\vee", Token::Operator(OperatorType::Or)),
+    ("°", Token::Operator(OperatorType::Top)),
+    ("⊤", Token::Operator(OperatorType::Top)),
+    ("\\top", Token::Operator(OperatorType::Top)),
+    ("_", Token::Operator(OperatorType::Bottom)),
+    ("⊥", Token::Operator(OperatorType::Bottom)),
+    ("\\bot", Token::Operator(OperatorType::Bottom)),
+    ("-", Token::Turnstile),
+    ("⊢", Token::Turnstile),
+    ("|-", Token::Turnstile),
+    ("\\vdash", Token::Turnstile),
+    (",", Token::Comma),
+    ("(", Token::Open),
+    (")", Token::Close),
+];
+
+/// Does the text start with this spelling. Spellings ending with a letter, like \to, must end a word:
+/// "\top" isn't "\to" followed by "p".
+fn spelled_at(text: &[char], spelling: &str) -> bool {
+    let len = spelling.chars().count();
+    if text.len() < len || !text.iter().zip(spelling.chars()).all(|(a, b)| *a == b) {
+        return false;
+    }
+
+    let ends_with_letter = spelling.chars().last().is_some_and(|c| c.is_alphabetic());
+    return !ends_with_letter || !text.get(len).is_some_and(|c| is_name_char(*c));
+}
+
+fn binary_operator(token: &Token) -> Option<OperatorType> {
+    match token {
+        Token::Operator(op) if [OperatorType::Impl, OperatorType::And, OperatorType::Or].contains(op) => Some(*op),
+        _ => None,
+    }
+}
+
+/// Implication is right associative: A > B > C is A > (B > C). Other operators are left associative.
+fn is_right_associative(op: OperatorType) -> bool {
+    op == OperatorType::Impl
+}
+
+/// Precedence climbing. Reads a formula whose binary operators have a priority below max_priority,
+/// or equal to it if allow_equal is set. Smaller priorities bind tighter, as in get_operator_priority.
+fn var_r(buff: &mut Cursor, vars: &mut Vec<String>, max_priority: f32, allow_equal: bool) -> Result<Formula, ParseError> {
+
+    let mut left = var_p(buff, vars)?;
+
+    while let Some(operator_type) = buff.peek_token().and_then(|(token, _)| binary_operator(&token)) {
+        let priority = get_operator_priority(operator_type);
+        if priority > max_priority || (priority == max_priority && !allow_equal) {
+            break;
+        }
+
+        buff.next_token();
+        let right = var_r(buff, vars, priority, is_right_associative(operator_type))?;
+
+        left = Formula::Operator(Operator {operator_type, arg1: Some(Box::new(left)), arg2: Some(Box::new(right))});
+    }
+
+    Ok(left)
+}
+
+fn var_p(buff: &mut Cursor, vars: &mut Vec<String>) -> Result<Formula, ParseError> {
+
+    match buff.peek_token().map(|(token, _)| token) {
+        Some(Token::Open) => {
+            buff.next_token();
+            let r = var_r(buff, vars, f32::INFINITY, true)?;
+            match buff.peek_token() {
+                Some((Token::Close, _)) => {
+                    buff.next_token();
+                    Ok(r)
+                },
+                _ => Err(buff.error("')'")),
+            }
+        },
+        Some(Token::Operator(OperatorType::Not)) => {
+            buff.next_token();
+            let p = var_p(buff, vars)?;
+            Ok(Formula::Operator(Operator {operator_type: OperatorType::Not, arg1: Some(Box::new(p)), arg2: None}))
+        },
+        Some(Token::Operator(operator_type @ (OperatorType::Top | OperatorType::Bottom))) => {
+            buff.next_token();
+            Ok(Formula::Operator(Operator {operator_type, arg1: None, arg2: None}))
+        },
+        Some(Token::Variable(name)) => {
+            let id = match vars.iter().position(|v| *v == name) {
+                Some(id) => id,
+                None => {
+                    if vars.len() as u32 >= MAX_VARIABLE_COUNT {
+                        return Err(buff.error(&format!("one of the {} variables already used", MAX_VARIABLE_COUNT)));
+                    }
+
+                    vars.push(name);
+                    vars.len() - 1
+                },
+            };
+
+            buff.next_token();
+            Ok(Formula::Variable(id as u32))
+        },
+        _ => Err(buff.error("a formula")),
+    }
+}
+
+/// Formulas separated by commas, up to the end or the given separator (that is consumed)
+fn formula_list(buff: &mut Cursor, vars: &mut Vec<String>, end: Option<Token>) -> Result<Vec<Formula>, ParseError> {
+    let mut res = Vec::new();
+
+    loop {
+        res.push(var_r(buff, vars, f32::INFINITY, true)?);
+
+        match buff.peek_token().map(|(token, _)| token) {
+            Some(Token::Comma) => { buff.next_token(); },
+            Some(token) if Some(&token) == end.as_ref() => {
+                buff.next_token();
+                return Ok(res);
+            },
+            None if end.is_none() && buff.peek().is_none() => return Ok(res),
+            _ => return Err(buff.error(match end {
+                Some(_) => "',' or '⊢'",
+                None => "',' or the end of the sequent",
+            })),
+        }
+    }
+}
+
+/// Parses a sequent like "A, B - A & B", also written "A, B ⊢ A ∧ B", "A, B |- A /\ B" or "A, B \vdash A \land B".
+/// first_column is the column of the text in the line, for errors.
+pub fn parse_sequent(seq: &str, file: &str, ligne_number: usize, first_column: usize) -> Result<Sequent, ParseError> {
+    let buff = &mut Cursor::new(seq, file, ligne_number, first_column);
+    let mut vars = Vec::new();
+
+    let before = if buff.peek_token().is_some_and(|(token, _)| token == Token::Turnstile) {
+        buff.next_token();
+        Vec::new()
+    } else {
+        formula_list(buff, &mut vars, Some(Token::Turnstile))?
+    };
+
+    let after = formula_list(buff, &mut vars, None)?;
+
+    return Ok(Sequent {before, after, variables: vars});
+}
+
+/// Number between 0 and max
+fn parse_number(text: &str, max: usize, expected: &str, file: &str, ligne_number: usize, first_column: usize) -> Result<usize, ParseError> {
+    match text.trim().parse::<usize>() {
+        Ok(u) if u <= max => Ok(u),
+        _ => {
+            let mut buff = Cursor::new(text, file, ligne_number, first_column);
+            Err(buff.error(expected))
+        },
+    }
+}
+
+/// Parses a level: id; "name"; sequent; difficulty; raa;
+pub fn parse_ligne(ligne: &str, file: &str, ligne_number: usize) -> Result<Level, ParseError> {
+
+    let infos: Vec<&str> = ligne.split(';').collect();
+
+    // Column of the start of each field
+    let mut columns = Vec::with_capacity(infos.len());
+    let mut column = 1;
+    for info in infos.iter() {
+        columns.push(column);
+        column += info.chars().count() + 1;
+    }
+
+    if infos.len() != 6 {
+        let field_count = usize::min(infos.len(), 6);
+        let rest = if infos.len() < 6 { "" } else { ";" };
+        let mut buff = Cursor::new(rest, file, ligne_number, columns[field_count - 1] + infos[field_count - 1].chars().count());
+
+        return Err(buff.error(if infos.len() < 6 { "';'" } else { "the end of the line" }));
+    }
+
+    let mut result = Level::empty();
+
+    result.id = parse_number(infos[0], usize::MAX, "a level id", file, ligne_number, columns[0])?;
+
+    result.name = infos[1].trim().replace("\"", "").to_string();
+
+    result.seq = parse_sequent(infos[2], file, ligne_number, columns[2])?;
+
+    let reffs = [ Difficulty::Immediate, Difficulty::Easy, Difficulty::Medium, Difficulty::Hard ];
+    result.difficulty = reffs[parse_number(infos[3], reffs.len() - 1, "a difficulty between 0 and 3", file, ligne_number, columns[3])?].clone();
+
+    result.raa = parse_number(infos[4], 1, "0 or 1 for RAA", file, ligne_number, columns[4])? == 1;
+
+    Ok(result)
+}
+
+/// Reads all the levels of a campaign in the legacy .sq format, one level per line.
+/// Levels with errors are skipped, and their errors are kept in the campaign.
+pub fn parse_campaign(name: &str, contents: &str) -> Campaign {
+    let lignes: Vec<&str> = contents.split('\n').collect();
+
+    let mut levels = Vec::with_capacity(lignes.len());
+    let mut errors = Vec::new();
+
+    for (i, ligne) in lignes.iter().enumerate() {
+        let ligne = ligne.trim_end_matches('\r');
+
+        if ligne.get(0..1) != Some("#") && ligne.trim() != "" {
+            match parse_ligne(ligne, name, i+1) {
+                Ok(level) => levels.push(level),
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+
+    return Campaign {
+        name: name.to_string(),
+        order: 0,
+        unlock: Unlock::default(),
+        levels,
+        errors,
+    };
+}
+
+
+/// Campaign in the structured format (.ron files)
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CampaignFile {
+    name: String,
+    #[serde(default)]
+    order: i32,
+    #[serde(default)]
+    unlock: Unlock,
+    levels: Vec<LevelFile>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct LevelFile {
+    id: usize,
+    name: String,
+    #[serde(default)]
+    description: String,
+    /// In any syntax accepted by parse_sequent
+    sequent: String,
+    difficulty: Difficulty,
+    #[serde(default)]
+    raa: bool,
+    /// Names of the rules, as displayed in game. All the rules are allowed if missing.
+    #[serde(default)]
+    allowed_rules: Option<Vec<String>>,
+    #[serde(default)]
+    banned_rules: Vec<String>,
+    #[serde(default)]
+    hints: Vec<String>,
+    #[serde(default)]
+    par_time: Option<f32>,
+    #[serde(default)]
+    par_steps: Option<u32>,
+    #[serde(default)]
+    author: Option<String>,
+}
+
+/// Reads a campaign in the structured format. The file must be valid ron, but levels with a wrong sequent
+/// or an unknown rule are skipped like in parse_campaign. Their errors tell the id of the level and the field.
+pub fn parse_structured_campaign(name: &str, contents: &str) -> Result<Campaign, LoadError> {
+    let file: CampaignFile = ron::from_str(contents).map_err(|e| LoadError {
+        message: format!("Error while loading level file {}: {}", name, e),
+    })?;
+
+    let mut levels = Vec::with_capacity(file.levels.len());
+    let mut errors = Vec::new();
+
+    let system = crate::proof::natural_logic::get_system();
+    let rule_names: Vec<String> = system.rules.iter().chain(system.special_rules.iter().flatten())
+        .map(|rule| rule.display_text().to_string())
+        .collect();
+
+    for level in file.levels {
+        let seq = parse_sequent(&level.sequent, name, 1, 1).map_err(|e| ParseError { field: Some((level.id, String::from("sequent"))), ..e });
+        let unknown_rule = level.allowed_rules.iter().flatten().map(|r| ("allowed_rules", r))
+            .chain(level.banned_rules.iter().map(|r| ("banned_rules", r)))
+            .find(|(_, r)| !rule_names.contains(r));
+
+        match (seq, unknown_rule) {
+            (Err(e), _) => errors.push(e),
+            (Ok(_), Some((field, rule))) => {
+                errors.push(ParseError {
+                    file: name.to_string(),
+                    field: Some((level.id, field.to_string())),
+                    line: 1,
+                    column: 1,
+                    expected: String::from("the name of a rule"),
+                    found: format!("{:?}", rule),
+                });
+            },
+            (Ok(seq), None) => levels.push(Level {
+                id: level.id,
+                name: level.name,
+                description: level.description,
+                seq,
+                difficulty: level.difficulty,
+                raa: level.raa,
+                rules: RuleFilter { allowed: level.allowed_rules, banned: level.banned_rules },
+                hints: level.hints,
+                par_time: level.par_time,
+                par_steps: level.par_steps,
+                author: level.author,
+                classical_only: false,
+            }),
+        }
+    }
+
+    return Ok(Campaign {
+        name: file.name,
+        order: file.order,
+        unlock: file.unlock,
+        levels,
+        errors,
+    });
+}
+
+/// Campaign in the structured format. Used by sequent-cli convert to convert legacy .sq files.
+/// The name is kept, so a converted campaign keeps its progress and the unlocks that refer to it.
+pub fn campaign_to_ron(campaign: &Campaign) -> String {
+    let file = CampaignFile {
+        name: campaign.name.clone(),
+        order: campaign.order,
+        unlock: campaign.unlock.clone(),
+        levels: campaign.levels.iter().map(|level| LevelFile {
+            id: level.id,
+            name: level.name.clone(),
+            description: level.description.clone(),
+            sequent: sequent_text(&level.seq, Symbols::Ascii),
+            difficulty: level.difficulty.clone(),
+            raa: level.raa,
+            allowed_rules: level.rules.allowed.clone(),
+            banned_rules: level.rules.banned.clone(),
+            hints: level.hints.clone(),
+            par_time: level.par_time,
+            par_steps: level.par_steps,
+            author: level.author.clone(),
+        }).collect(),
+    };
+
+    return ron::ser::to_string_pretty(&file, ron::ser::PrettyConfig::default()).unwrap_or_default();
+}
+
+/// Reads a campaign, in the structured format if the file ends with .ron, in the legacy format otherwise
+pub fn parse_file(path: &str) -> Result<Campaign, LoadError> {
+
+    let contents = fs::read_to_string(path).map_err(|e| LoadError {
+        message: format!("Error while loading level file {}: {}", path, e),
+    })?;
+
+    let name = Path::new(path).file_name().and_then(|name| name.to_str()).unwrap_or(path);
+
+    if path.ends_with(".ron") {
+        return parse_structured_campaign(name, &contents);
+    }
+    return Ok(parse_campaign(name, &contents));
+}
+
+/// Problems that can't be seen when parsing: duplicated levels, false or unprovable sequents and wrong RAA flags.
+/// Also sets classical_only on the levels.
+pub fn check_campaign(campaign: &mut Campaign) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    for (i, level) in campaign.levels.iter().enumerate() {
+        let previous = &campaign.levels[..i];
+
+        if previous.iter().any(|l| l.id == level.id) {
+            warnings.push(format!("Several levels of {} have the id {}", campaign.name, level.id));
+        }
+        // Variable names don't change the level
+        if let Some(other) = previous.iter().find(|l| l.seq.before == level.seq.before && l.seq.after == level.seq.after) {
+            warnings.push(format!("Level {} of {} has the same sequent as level {}", level.id, campaign.name, other.id));
+        }
+    }
+
+    for level in campaign.levels.iter_mut() {
+        // Sanity check: a level that is not a tautology can't be solved
+        match crate::proof::truth_table::truth_table(&level.seq) {
+            Some(table) if !table.is_tautology() => {
+                warnings.push(format!("Level {} of {} is false, it can't be solved!", level.id, campaign.name));
+                continue;
+            },
+            _ => { },
+        }
+
+        // Make sure the level can be solved with the rules it allows
+        if crate::proof::prover::find_proof_with_rules(&level.seq, &level.rules, 0.0, &mut 0).is_none() {
+            warnings.push(format!("No proof found for level {} of {} with the rules it allows", level.id, campaign.name));
+        }
+
+        // Check the RAA flag
+        match crate::proof::intuitionistic::is_provable(&level.seq) {
+            Some(intuitionistic) => {
+                level.classical_only = !intuitionistic;
+
+                if level.raa == intuitionistic {
+                    warnings.push(format!("Level {} of {} is marked as {}needing RAA, but it can{} be solved without RAA", 
+                        level.id, campaign.name, if level.raa { "" } else { "not " }, if intuitionistic { "" } else { "'t" }));
+                }
+            },
+            None => { },
+        }
+    }
+
+    return warnings;
+}
+
+impl std::fmt::Display for Difficulty
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Difficulty::Immediate => "Immediate",
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::proof::test_utils::{TestRng, random_formula};
+    use crate::proof::text::formula_text;
+    use crate::variable_name;
+
+    fn var(id: u32) -> Formula { Formula::Variable(id) }
+    fn op(operator_type: OperatorType, arg1: Formula, arg2: Option<Formula>) -> Formula {
+        Formula::Operator(Operator { operator_type, arg1: Some(Box::new(arg1)), arg2: arg2.map(Box::new) })
+    }
+
+    fn sequent(text: &str) -> Result<Sequent, ParseError> {
+        parse_sequent(text, "test.sq", 1, 1)
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn error_at(column: usize, expected: &str, found: &str) -> ParseError {
+        ParseError { file: String::from("test.sq"), field: None, line: 1, column, expected: expected.to_string(), found: found.to_string() }
+    }
+
+    #[test]
+    fn sequents() {
+        assert_eq!(sequent("A - !!A"), Ok(Sequent {
+            before: vec![var(0)],
+            after: vec![op(OperatorType::Not, op(OperatorType::Not, var(0), None), None)],
+            variables: names(&["A"]),
+        }));
+        assert_eq!(sequent(" -A|(B&C)"), Ok(Sequent {
+            before: vec![],
+            after: vec![op(OperatorType::Or, var(0), Some(op(OperatorType::And, var(1), Some(var(2)))))],
+            variables: names(&["A", "B", "C"]),
+        }));
+        assert_eq!(sequent("B, A > _ - !A, °"), Ok(Sequent {
+            before: vec![var(0), op(OperatorType::Impl, var(1), Some(Formula::Operator(Operator { operator_type: OperatorType::Bottom, arg1: None, arg2: None })))],
+            after: vec![op(OperatorType::Not, var(1), None), Formula::Operator(Operator { operator_type: OperatorType::Top, arg1: None, arg2: None })],
+            variables: names(&["B", "A"]),
+        }));
+    }
+
+    #[test]
+    fn named_variables() {
+        assert_eq!(sequent("rain > wet, rain - wet"), Ok(Sequent {
+            before: vec![op(OperatorType::Impl, var(0), Some(var(1))), var(0)],
+            after: vec![var(1)],
+            variables: names(&["rain", "wet"]),
+        }));
+        assert_eq!(sequent("P1 & P2 ⊢ P2"), Ok(Sequent {
+            before: vec![op(OperatorType::And, var(0), Some(var(1)))],
+            after: vec![var(1)],
+            variables: names(&["P1", "P2"]),
+        }));
+
+        // Names that start like a constant or an operator
+        assert_eq!(sequent("Tx, False |- ~to").unwrap().variables, names(&["Tx", "False", "to"]));
+        assert_eq!(sequent("A1 - A 1"), Err(error_at(8, "',' or the end of the sequent", "'1'")));
+        assert_eq!(sequent("- 1A"), Err(error_at(3, "a formula", "'1'")));
+
+        let s = sequent("!(rain | snow) - !rain & !snow").unwrap();
+        assert_eq!(sequent_text(&s, Symbols::Ascii), "~(rain \\/ snow) |- ~rain /\\ ~snow");
+        assert_eq!(variable_name(&s.variables, 2), "A");
+    }
+
+    #[test]
+    fn sequent_errors() {
+        assert_eq!(sequent("A - (A & B"), Err(error_at(11, "')'", "nothing")));
+        assert_eq!(sequent("A - A & * B"), Err(error_at(9, "a formula", "'*'")));
+        assert_eq!(sequent("A B - A"), Err(error_at(3, "',' or '⊢'", "'B'")));
+        assert_eq!(sequent("A - "), Err(error_at(5, "a formula", "nothing")));
+        assert_eq!(sequent("A - B)"), Err(error_at(6, "',' or the end of the sequent", "')'")));
+        assert_eq!(sequent("A - B & C)"), Err(error_at(10, "',' or the end of the sequent", "')'")));
+        assert_eq!(sequent("A - B & > C"), Err(error_at(9, "a formula", "'>'")));
+        assert!(sequent("A, B, C, D, E, F, G, H, I, J - K").is_err());
+        assert_eq!(sequent("A - \\top \\lnt A"), Err(error_at(10, "',' or the end of the sequent", "'\\'")));
+    }
+
+    #[test]
+    fn input_syntaxes() {
+        let expected = sequent("A > B, !B - !A | (_ & °)");
+        assert_eq!(sequent("A → B, ¬B ⊢ ¬A ∨ (⊥ ∧ ⊤)"), expected);
+        assert_eq!(sequent("A -> B, ~B |- ~A \\/ (\\bot /\\ \\top)"), expected);
+        assert_eq!(sequent("$A \\to B, \\lnot B \\vdash \\neg A \\lor (\\bot \\land \\top)$"), expected);
+        assert_eq!(sequent("A\\rightarrow B,\\lnot B|-~A\\vee(\\bot\\wedge\\top)"), expected);
+
+        // Longest spelling first
+        assert_eq!(sequent("A->B|-A|B"), sequent("A > B - A | B"));
+        assert_eq!(sequent("A |-B"), sequent("A - B"));
+
+        // T and F are variables, whatever the spelling of ⊢
+        let expected = Ok(Sequent {
+            before: vec![var(0), var(1)],
+            after: vec![op(OperatorType::And, var(1), Some(var(2)))],
+            variables: names(&["E", "F", "T"]),
+        });
+        assert_eq!(sequent("E, F - F & T"), expected);
+        assert_eq!(sequent("E, F |- F /\\ T"), expected);
+
+        // Letters of other names aren't read as constants or commands
+        assert_eq!(sequent("- \\top"), sequent("- °"));
+        assert_eq!(sequent("- \\toA"), Err(error_at(3, "a formula", "'\\'")));
+    }
+
+    #[test]
+    fn precedence() {
+        let formula = |text: &str| {
+            let s = sequent(&format!("- {}", text)).unwrap();
+            formula_text(&s.after[0], &s.variables, Symbols::Ascii)
+        };
+
+        // Implication is right associative
+        assert_eq!(formula("A > B > C"), "A -> (B -> C)");
+        assert_eq!(formula("(A > B) > C"), "(A -> B) -> C");
+
+        // Other operators are left associative
+        assert_eq!(formula("A & B & C"), r"(A /\ B) /\ C");
+        assert_eq!(formula("A | B & C"), r"(A \/ B) /\ C");
+
+        assert_eq!(formula("!A & B > C | !!D"), r"~A /\ B -> C \/ ~(~D)");
+        assert_eq!(formula("A & B > _ > °"), r"A /\ B -> (\bot -> \top)");
+        assert_eq!(formula(" ! ( A>B ) &C "), r"~(A -> B) /\ C");
+    }
+
+    /// Renames the variables in the order they appear, like the parser does
+    fn rename_variables(f: &Formula, names: &mut HashMap<u32, u32>) -> Formula {
+        match f {
+            Formula::Operator(operator) => Formula::Operator(Operator {
+                operator_type: operator.operator_type,
+                arg1: operator.arg1.as_ref().map(|f| Box::new(rename_variables(f, names))),
+                arg2: operator.arg2.as_ref().map(|f| Box::new(rename_variables(f, names))),
+            }),
+            Formula::Variable(id) => {
+                let new_id = names.len() as u32;
+                Formula::Variable(*names.entry(*id).or_insert(new_id))
+            },
+            Formula::NotCompleted(_) => f.clone(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = TestRng(0x5eb7);
+
+        for _ in 0..1000 {
+            let f = rename_variables(&random_formula(&mut rng, 5, MAX_VARIABLE_COUNT), &mut HashMap::new());
+            let s = Sequent { before: vec![f.clone()], after: vec![f], variables: Vec::new() };
+
+            // Unnamed variables are read back with the names they were written with
+            let text = sequent_text(&s, Symbols::Ascii);
+            let read = sequent(&text).unwrap();
+            assert_eq!((&read.before, &read.after), (&s.before, &s.after), "{}", text);
+            assert_eq!(sequent_text(&read, Symbols::Ascii), text);
+        }
+    }
+
+    #[test]
+    fn lines() {
+        let level = parse_ligne("3;  \"Distribution\";  A | (B & C) - (A | B) & (A | C);  2;  1;", "test.sq", 1).unwrap();
+        assert_eq!(level.id, 3);
+        assert_eq!(level.name, "Distribution");
+        assert_eq!(level.seq.before.len(), 1);
+        assert_eq!(level.difficulty.to_string(), "Medium");
+        assert!(level.raa);
+
+        assert_eq!(parse_ligne("1; \"A\"; A - A; 1", "test.sq", 1).err(), Some(error_at(17, "';'", "nothing")));
+        assert_eq!(parse_ligne("1; \"A\"; A - A; 1; 0; ; ", "test.sq", 1).err(), Some(error_at(22, "the end of the line", "';'")));
+        assert_eq!(parse_ligne("x; \"A\"; A - A; 1; 0;", "test.sq", 1).err(), Some(error_at(1, "a level id", "'x'")));
+        assert_eq!(parse_ligne("1; \"A\"; A - A;  7; 0;", "test.sq", 1).err(), Some(error_at(17, "a difficulty between 0 and 3", "'7'")));
+        assert_eq!(parse_ligne("1; \"A\"; A - A; 1; 2;", "test.sq", 1).err(), Some(error_at(19, "0 or 1 for RAA", "'2'")));
+        assert_eq!(parse_ligne("1; \"A\"; A - (A; 1; 0;", "test.sq", 1).err(), Some(error_at(15, "')'", "nothing")));
+    }
+
+    #[test]
+    fn bad_levels_are_skipped() {
+        let campaign = parse_campaign("test.sq", "# Comment\n1; \"A\"; A - A; 1; 0;\r\n2; \"B\"; A - ; 1; 0;\n\n3; \"C\"; A - !!A; 1; 0;\n");
+
+        assert_eq!(campaign.levels.iter().map(|l| l.id).collect::<Vec<usize>>(), vec![1, 3]);
+        assert_eq!(campaign.errors, vec![ParseError {
+            file: String::from("test.sq"), field: None, line: 3, column: 13, expected: String::from("a formula"), found: String::from("nothing"),
+        }]);
+        assert_eq!(campaign.errors[0].to_string(), "test.sq:3:13: expected a formula, found nothing");
+    }
+
+    #[test]
+    fn campaign_checks() {
+        let mut campaign = parse_campaign("test.sq", "1; \"A\"; A - A; 0; 0;\n2; \"B\"; !!A - A; 1; 0;\n2; \"C\"; B - B; 0; 0;\n4; \"D\"; A - B; 0; 0;\n5; \"E\"; A - !!A; 1; 1;\n");
+
+        assert_eq!(check_campaign(&mut campaign), vec![
+            "Several levels of test.sq have the id 2",
+            "Level 2 of test.sq has the same sequent as level 1",
+            "Level 2 of test.sq is marked as not needing RAA, but it can't be solved without RAA",
+            "Level 4 of test.sq is false, it can't be solved!",
+            "Level 5 of test.sq is marked as needing RAA, but it can be solved without RAA",
+        ]);
+        assert_eq!(campaign.levels.iter().map(|l| l.classical_only).collect::<Vec<bool>>(), vec![false, true, false, false, false]);
+    }
+
+    const STRUCTURED_CAMPAIGN: &str = r#"(
+    name: "Rain",
+    order: 2,
+    unlock: (campaigns: ["intro.sq"]),
+    levels: [
+        (
+            id: 1,
+            name: "Modus ponens",
+            description: "If it rains, the street is wet.",
+            sequent: "rain → wet, rain ⊢ wet",
+            difficulty: Easy,
+            allowed_rules: Some(["→e", "Ax"]),
+            hints: ["Use the implication"],
+            par_time: Some(20.0),
+            par_steps: Some(2),
+            author: Some("Jane"),
+        ),
+        (id: 2, name: "Broken", sequent: "rain ⊢ (wet", difficulty: Easy),
+        (id: 3, name: "Excluded middle", sequent: "\\vdash rain \\lor \\lnot rain", difficulty: Hard, raa: true, banned_rules: ["∨i"]),
+        (id: 4, name: "Unknown rule", sequent: "A ⊢ A", difficulty: Easy, banned_rules: ["Ax", "cut"]),
+    ],
+)"#;
+
+    #[test]
+    fn structured_campaign() {
+        let campaign = parse_structured_campaign("rain.ron", STRUCTURED_CAMPAIGN).unwrap_or_else(|e| panic!("{}", e.message));
+
+        assert_eq!(campaign.name, "Rain");
+        assert_eq!(campaign.order, 2);
+        assert_eq!(campaign.unlock, Unlock { campaigns: vec![String::from("intro.sq")], solved_levels: 0 });
+        assert_eq!(campaign.levels.iter().map(|l| l.id).collect::<Vec<usize>>(), vec![1, 3]);
+
+        let level = &campaign.levels[0];
+        assert_eq!(level.description, "If it rains, the street is wet.");
+        assert_eq!(sequent_text(&level.seq, Symbols::Ascii), "rain -> wet, rain |- wet");
+        assert_eq!(level.difficulty, Difficulty::Easy);
+        assert!(!level.raa);
+        assert_eq!(level.rules.allowed, Some(vec![String::from("→e"), String::from("Ax")]));
+        assert!(level.rules.allows("→e") && !level.rules.allows("→i"));
+        assert_eq!(level.hints, vec![String::from("Use the implication")]);
+        assert_eq!((level.par_time, level.par_steps, level.author.as_deref()), (Some(20.0), Some(2), Some("Jane")));
+
+        let level = &campaign.levels[1];
+        assert_eq!(sequent_text(&level.seq, Symbols::Ascii), "|- rain \\/ ~rain");
+        assert!(level.raa);
+        assert_eq!(level.rules, RuleFilter { allowed: None, banned: vec![String::from("∨i")] });
+        assert!(level.rules.allows("RAA") && !level.rules.allows("∨i"));
+
+        // Level and field of the error
+        assert_eq!(campaign.errors[0].to_string(), "rain.ron: level 2, sequent, column 12: expected ')', found nothing");
+        assert_eq!(campaign.errors[1].to_string(), "rain.ron: level 4, banned_rules, column 1: expected the name of a rule, found \"cut\"");
+
+        assert!(parse_structured_campaign("rain.ron", "(name: \"Rain\", levels: [(id: 1)])").is_err());
+    }
+
+    #[test]
+    fn level_errors() {
+        let contents = concat!(
+            "(name: \"Errors\", levels: [\n",
+            "    (id: 1, allowed_rules: Some([\"cut\"]), name: \"Rules first\", sequent: \"A ⊢ A\", difficulty: Easy),\n",
+            "    (id: 2, name: \"Escapes\", sequent: \"\\\\vdash \\u{2192}A (B\", difficulty: Easy),\n",
+            "    (id: 5, name: \"A ⊢ (A\", sequent: \"A ⊢ (A\", difficulty: Easy, banned_rules: [\"cut\"]),\n",
+            "])",
+        );
+        let campaign = parse_structured_campaign("errors.ron", contents).unwrap_or_else(|e| panic!("{}", e.message));
+
+        assert_eq!(campaign.errors.iter().map(|e| e.to_string()).collect::<Vec<String>>(), vec![
+            "errors.ron: level 1, allowed_rules, column 1: expected the name of a rule, found \"cut\"",
+            "errors.ron: level 2, sequent, column 8: expected a formula, found '→'",
+            "errors.ron: level 5, sequent, column 7: expected ')', found nothing",
+        ]);
+
+        let error = parse_structured_campaign("errors.ron", "(name: \"Errors\", levels: [(id: 1)])").err().unwrap();
+        assert!(error.message.starts_with("Error while loading level file errors.ron: "), "{}", error.message);
+    }
+
+    #[test]
+    fn legacy_conversion() {
+        let legacy = parse_campaign("test.sq", "1; \"A\"; A - !!A; 1; 0;\n2; \"Tiers exclu\"; - A | !A; 3; 1;\n");
+        let converted = parse_structured_campaign("test.ron", &campaign_to_ron(&legacy)).unwrap_or_else(|e| panic!("{}", e.message));
+
+        assert_eq!(converted.name, "test.sq");
+        assert!(converted.errors.is_empty());
+        for (a, b) in legacy.levels.iter().zip(converted.levels.iter()) {
+            assert_eq!((a.id, &a.name, &a.seq, &a.difficulty, a.raa), (b.id, &b.name, &b.seq, &b.difficulty, b.raa));
+        }
+        assert_eq!(converted.levels.len(), 2);
+    }
+
+    #[test]
+    fn level_files() {
+        for file in fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/levels")).unwrap() {
+            let campaign = parse_file(file.unwrap().path().to_str().unwrap()).unwrap_or_else(|e| panic!("{}", e.message));
+            assert!(campaign.errors.is_empty(), "{}", campaign.errors[0]);
+        }
+    }
+}
@@ -0,0 +1,373 @@
+// Reimplementation of the Tp 6 on Sequent calculation
+use crate::*;
+
+// A little change in the struct to help with the proofs
+#[derive(Clone)]
+pub struct SequentCalc {
+    pub before: Vec<Formula>,
+    pub before_var: Vec<Formula>,
+    pub after: Vec<Formula>,
+    pub after_var: Vec<Formula>,
+}
+
+// Rule of the sequent calculus. The operator rules decompose their principal formula, on the left or on the right of ⊢
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalcRule {
+    // The principal formula is on both sides
+    Axiom,
+    // ⊥ on the left
+    Bottom,
+    // ⊤ on the right
+    Top,
+    Left(OperatorType),
+    Right(OperatorType),
+}
+
+// Derivation of a sequent in the sequent calculus, that can be checked with is_valid
+#[derive(Clone, Debug)]
+pub struct CalcProof {
+    pub sequent: Sequent,
+    pub rule: CalcRule,
+    pub principal: Formula,
+    pub premises: Vec<CalcProof>,
+}
+
+#[derive(Clone)]
+pub enum CalcResult {
+    // The sequent is true, with its derivation
+    Provable(CalcProof),
+    // The sequent is false with this valuation
+    CounterModel(Valuation),
+}
+
+impl CalcResult {
+    pub fn is_provable(&self) -> bool {
+        matches!(self, CalcResult::Provable(_))
+    }
+}
+
+// Transform a sequent to the new format
+fn sequent_to_sequentcalc(sequent: Sequent) -> SequentCalc{
+    SequentCalc {
+        before: sequent.before,
+        before_var: vec![],
+        after: sequent.after,
+        after_var: vec![],
+    }
+}
+
+
+// Transform a branch back to a sequent, to put it in the derivation
+fn sequentcalc_to_sequent(seq: &SequentCalc, variables: &[String]) -> Sequent{
+    Sequent {
+        before: seq.before_var.iter().chain(seq.before.iter()).cloned().collect(),
+        after: seq.after_var.iter().chain(seq.after.iter()).cloned().collect(),
+        variables: variables.to_vec(),
+    }
+}
+
+
+// Verify if a formula is contain in a vector of formulas
+fn member(formula: &Formula, list: &[Formula]) -> bool{
+    for elmt in list.iter() {if elmt == formula {return true}}
+    false
+}
+
+// Check if the bottom rule can be applied
+fn bottom_calc(seq: &SequentCalc) -> bool{
+    let bottom = Formula::Operator(Operator {operator_type: OperatorType::Bottom, arg1:  None, arg2: None}); 
+    member(&bottom, &seq.before) || member(&bottom, &seq.before_var)
+}
+
+
+// Check if the top rule can be applied
+fn top_calc(seq: &SequentCalc) -> bool{
+    let top = Formula::Operator(Operator {operator_type: OperatorType::Top, arg1:  None, arg2: None}); 
+    member(&top, &seq.after) || member(&top, &seq.after_var)
+}
+
+// Check if the axiom rule can be applied, and give the formula on both sides
+fn axiom_calc(seq: &SequentCalc) -> Option<Formula>{
+    for elmt in seq.before.iter(){ if member(elmt, &seq.after) || member(elmt, &seq.after_var) {return Some(elmt.clone())}}
+    for elmt in seq.before_var.iter(){ if member(elmt, &seq.after) || member(elmt, &seq.after_var) {return Some(elmt.clone())}}
+    None
+}
+
+
+// Find the rule that closes the branch and its principal formula, if any
+fn closing_axiom(seq: &SequentCalc) -> Option<(CalcRule, Formula)>{
+    if bottom_calc(seq) {return Some((CalcRule::Bottom, Formula::Operator(Operator {operator_type: OperatorType::Bottom, arg1: None, arg2: None})))}
+    if top_calc(seq) {return Some((CalcRule::Top, Formula::Operator(Operator {operator_type: OperatorType::Top, arg1: None, arg2: None})))}
+    axiom_calc(seq).map(|formula| (CalcRule::Axiom, formula))
+}
+
+// Formulas added to each premise when the principal formula is removed, on the left and on the right of ⊢
+fn decomposition(operator: &Operator, left: bool) -> Vec<(Vec<Formula>, Vec<Formula>)>{
+    let arg = |child: &Option<Box<Formula>>| child.as_deref().cloned().expect("The operator has all its children");
+
+    match (operator.operator_type, left) {
+        (OperatorType::Not, true) => vec![(vec![], vec![arg(&operator.arg1)])],
+        (OperatorType::Not, false) => vec![(vec![arg(&operator.arg1)], vec![])],
+        (OperatorType::Impl, true) => vec![(vec![], vec![arg(&operator.arg1)]), (vec![arg(&operator.arg2)], vec![])],
+        (OperatorType::Impl, false) => vec![(vec![arg(&operator.arg1)], vec![arg(&operator.arg2)])],
+        (OperatorType::And, true) => vec![(vec![arg(&operator.arg1), arg(&operator.arg2)], vec![])],
+        (OperatorType::And, false) => vec![(vec![], vec![arg(&operator.arg1)]), (vec![], vec![arg(&operator.arg2)])],
+        (OperatorType::Or, true) => vec![(vec![arg(&operator.arg1)], vec![]), (vec![arg(&operator.arg2)], vec![])],
+        (OperatorType::Or, false) => vec![(vec![], vec![arg(&operator.arg1), arg(&operator.arg2)])],
+        // ⊤ on the left and ⊥ on the right can be removed, the other cases close the branch
+        (OperatorType::Top | OperatorType::Bottom, _) => vec![(vec![], vec![])],
+    }
+}
+
+// Same formulas, in any order
+fn same_formulas(a: &[Formula], b: &[Formula]) -> bool{
+    let mut rest = b.to_vec();
+    a.len() == b.len() && a.iter().all(|f| match rest.iter().position(|g| g == f) {
+        Some(i) => {rest.swap_remove(i); true},
+        None => false,
+    })
+}
+
+impl CalcProof {
+    // Check that every step of the derivation applies its rule
+    pub fn is_valid(&self) -> bool{
+        let s = &self.sequent;
+        match self.rule {
+            CalcRule::Axiom => self.premises.is_empty() && s.before.contains(&self.principal) && s.after.contains(&self.principal),
+            CalcRule::Bottom => self.premises.is_empty() && s.before.contains(&self.principal)
+                && matches!(&self.principal, Formula::Operator(o) if o.operator_type == OperatorType::Bottom),
+            CalcRule::Top => self.premises.is_empty() && s.after.contains(&self.principal)
+                && matches!(&self.principal, Formula::Operator(o) if o.operator_type == OperatorType::Top),
+            CalcRule::Left(op_type) | CalcRule::Right(op_type) => {
+                let left = matches!(self.rule, CalcRule::Left(_));
+                let Formula::Operator(operator) = &self.principal else { return false };
+                if operator.operator_type != op_type {return false}
+
+                let side = if left {&s.before} else {&s.after};
+                let Some(i) = side.iter().position(|f| *f == self.principal) else { return false };
+                let mut rest = side.clone();
+                rest.remove(i);
+                let (before, after) = if left {(rest, s.after.clone())} else {(s.before.clone(), rest)};
+
+                let added = decomposition(operator, left);
+                added.len() == self.premises.len() && added.iter().zip(self.premises.iter()).all(|((l, r), premise)| {
+                    same_formulas(&[before.clone(), l.clone()].concat(), &premise.sequent.before)
+                        && same_formulas(&[after.clone(), r.clone()].concat(), &premise.sequent.after)
+                        && premise.is_valid()
+                })
+            },
+        }
+    }
+}
+
+// Node of the derivation while it is built, the premises are indices in the list of nodes
+struct CalcNode {
+    sequent: Sequent,
+    step: Option<(CalcRule, Formula)>,
+    premises: Vec<usize>,
+}
+
+fn build_proof(nodes: &[CalcNode], id: usize) -> CalcProof{
+    let node = &nodes[id];
+    let (rule, principal) = node.step.clone().expect("Every branch of a proven sequent is closed");
+    CalcProof {
+        sequent: node.sequent.clone(),
+        rule,
+        principal,
+        premises: node.premises.iter().map(|p| build_proof(nodes, *p)).collect(),
+    }
+}
+
+// Valuation that makes a branch with only variables false: true on the left, false on the right
+fn counter_model(seq: &SequentCalc) -> Valuation{
+    let mut res = Valuation::new();
+    for elmt in seq.before_var.iter(){ if let Formula::Variable(v) = elmt {res.insert(*v, true);} }
+    for elmt in seq.after_var.iter(){ if let Formula::Variable(v) = elmt {res.insert(*v, false);} }
+    res
+}
+
+
+// Advance the calculation by one step, by mutating the queue. Each branch comes with its node in the derivation.
+// return Some bool (wether or not the original sequent was true) if the proof is finished
+// return None otherwise
+fn step_one_calc(queue: &mut Vec<(SequentCalc, usize)>, nodes: &mut Vec<CalcNode>, variables: &[String]) -> Option<bool>{
+    let (mut seq, node) = match queue.pop() {
+        Some(elmt) => elmt,
+        None => return Some(true)   // All branches have been proven !
+    };
+    if let Some(step) = closing_axiom(&seq) { // End of this branch
+        nodes[node].step = Some(step);
+        return None
+    }
+
+    let (formula, left) = match seq.before.pop() {
+        Some(elmt) => (elmt, true),
+        None => match seq.after.pop() {
+            Some(elmt) => (elmt, false),
+            None => { // The branch isn't provable, keep it to get a counter model
+                queue.push((seq, node));
+                return Some(false)
+            },
+        },
+    };
+
+    match &formula {
+        Formula::Operator(operator) => {
+            for (added_before, added_after) in decomposition(operator, left) {
+                let mut premise = seq.clone();
+                premise.before.extend(added_before);
+                premise.after.extend(added_after);
+
+                nodes.push(CalcNode { sequent: sequentcalc_to_sequent(&premise, variables), step: None, premises: vec![] });
+                let id = nodes.len() - 1;
+                nodes[node].premises.push(id);
+                queue.push((premise, id));
+            }
+            let rule = if left {CalcRule::Left(operator.operator_type)} else {CalcRule::Right(operator.operator_type)};
+            nodes[node].step = Some((rule, formula));
+        },
+        Formula::Variable(_variable) => { // Same sequent, the variable waits for the axiom
+            if left {seq.before_var.push(formula)} else {seq.after_var.push(formula)}
+            queue.push((seq, node));
+        },
+        Formula::NotCompleted(_int) => unreachable!() // Fields are rejected by proof_or_fake
+    }
+    None
+}
+
+fn sequent_calculation(seq: Sequent) -> CalcResult{
+    let variables = seq.variables.clone();
+    let mut nodes = vec![CalcNode { sequent: seq.clone(), step: None, premises: vec![] }];
+    let mut queue = vec![(sequent_to_sequentcalc(seq), 0)];
+    loop {
+        match step_one_calc(&mut queue, &mut nodes, &variables) {
+            Some(true) => return CalcResult::Provable(build_proof(&nodes, 0)),
+            Some(false) => return CalcResult::CounterModel(counter_model(&queue.last().unwrap().0)),
+            None => continue,
+        }
+    }
+}
+
+// Add the variables of the formula that are not in the valuation yet, with the value false
+fn complete_valuation(formula: &Formula, valuation: &mut Valuation){
+    match formula {
+        Formula::Operator(operator) => {
+            for child in operator.arg1.iter().chain(operator.arg2.iter()) {complete_valuation(child, valuation)}
+        },
+        Formula::Variable(v) => {valuation.entry(*v).or_insert(false);},
+        Formula::NotCompleted(_) => (),
+    }
+}
+
+// Decide if the sequent is provable in classical logic. If it is not, gives a value to every variable of the sequent so that it is false
+// return None if the sequent still has fields to complete
+pub fn proof_or_fake(seq: Sequent) -> Option<CalcResult>{
+    if !is_sequent_completed(&seq) {return None}
+
+    let formulas: Vec<Formula> = seq.before.iter().chain(seq.after.iter()).cloned().collect();
+
+    match sequent_calculation(seq) {
+        CalcResult::CounterModel(mut valuation) => {
+            for formula in formulas.iter() {complete_valuation(formula, &mut valuation)}
+            Some(CalcResult::CounterModel(valuation))
+        },
+        provable => Some(provable),
+    }
+}
+
+// Shortcut for proof_or_fake when only the answer is needed
+pub fn is_provable(seq: &Sequent) -> Option<bool>{
+    proof_or_fake(seq.clone()).map(|res| res.is_provable())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::test_utils::*;
+    use crate::proof::truth_table::*;
+
+    #[test]
+    fn excluded_middle_is_provable() {
+        let result = proof_or_fake(seq(vec![], or(not(a()), a()))).unwrap();
+
+        let CalcResult::Provable(proof) = result else { panic!("Expected a proof") };
+        assert!(proof.is_valid());
+        assert_eq!(proof.rule, CalcRule::Right(OperatorType::Or));
+        assert_eq!(proof.premises[0].rule, CalcRule::Right(OperatorType::Not));
+        assert_eq!(proof.premises[0].premises[0].rule, CalcRule::Axiom);
+        assert_eq!(proof.premises[0].premises[0].principal, a());
+        assert_eq!(proof.premises[0].premises[0].sequent.before, vec![a()]);
+    }
+
+    #[test]
+    fn wrong_derivations_are_rejected() {
+        let Some(CalcResult::Provable(proof)) = proof_or_fake(seq(vec![and(a(), b())], and(b(), a()))) else { panic!() };
+        assert!(proof.is_valid());
+
+        let mut wrong = proof.clone();
+        wrong.premises.pop();
+        assert!(!wrong.is_valid());
+
+        let mut wrong = proof.clone();
+        wrong.rule = CalcRule::Left(OperatorType::Or);
+        assert!(!wrong.is_valid());
+
+        let mut wrong = proof;
+        wrong.premises[0].premises[0].sequent.before = vec![b()];
+        assert!(!wrong.is_valid());
+    }
+
+    #[test]
+    fn counter_model_falsifies_sequent() {
+        // A → B, C ⊢ B is false when A and B are false and C is true
+        let result = proof_or_fake(seq(vec![imp(a(), b()), c()], b())).unwrap();
+
+        let CalcResult::CounterModel(valuation) = result else { panic!("Expected a counter model") };
+        assert_eq!(valuation, Valuation::from([(0, false), (1, false), (2, true)]));
+    }
+
+    #[test]
+    fn both_sides_of_and_are_checked() {
+        assert_eq!(is_provable(&seq(vec![a()], and(a(), b()))), Some(false));
+        assert_eq!(is_provable(&seq(vec![a()], and(b(), a()))), Some(false));
+        assert_eq!(is_provable(&seq(vec![a(), b()], and(b(), a()))), Some(true));
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(is_provable(&seq(vec![], top())), Some(true));
+        assert_eq!(is_provable(&seq(vec![bottom()], a())), Some(true));
+        assert_eq!(is_provable(&seq(vec![top()], bottom())), Some(false));
+        assert_eq!(is_provable(&seq(vec![a()], or(bottom(), a()))), Some(true));
+        assert_eq!(is_provable(&seq(vec![imp(a(), bottom())], not(a()))), Some(true));
+
+        // The counter model does not contain the constants
+        let Some(CalcResult::CounterModel(valuation)) = proof_or_fake(seq(vec![top(), a()], or(bottom(), b()))) else { panic!() };
+        assert_eq!(valuation, Valuation::from([(0, true), (1, false)]));
+    }
+
+    #[test]
+    fn fields_are_rejected() {
+        assert!(proof_or_fake(seq(vec![a()], and(a(), field(0)))).is_none());
+    }
+
+    #[test]
+    fn agrees_with_truth_tables() {
+        let mut rng = TestRng(0x5eed);
+        let var_count = 3;
+
+        for _ in 0..2000 {
+            let s = random_sequent(&mut rng, 3, var_count);
+
+            match proof_or_fake(s.clone()).unwrap() {
+                CalcResult::Provable(proof) => {
+                    assert!(truth_table(&s).unwrap().is_tautology(), "{:?} is not a tautology", s);
+                    assert!(proof.is_valid(), "Wrong derivation of {:?}", s);
+                },
+                CalcResult::CounterModel(valuation) => {
+                    assert_eq!(eval_sequent(&s, &valuation), Some(false), "{:?} is not a counter model of {:?}", valuation, s);
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+// Decision procedure for intuitionistic logic, that is natural deduction without RAA.
+// Uses the contraction-free sequent calculus G4ip (Dyckhoff, 1992): every rule makes the sequent smaller, so the search always terminates without loop checks.
+use super::*;
+
+/// Decide if the sequent is provable without RAA. Several formulas on the right are read as a disjunction.
+/// Returns None if the sequent still has fields to complete.
+pub fn is_provable(seq: &Sequent) -> Option<bool> {
+    if !is_sequent_completed(seq) {
+        return None;
+    }
+
+    let context = seq.before.iter().map(without_not).collect();
+    let goal = seq.after.iter().map(without_not).reduce(|a, b| binary(OperatorType::Or, a, b)).unwrap_or(falsum());
+
+    return Some(prove(context, goal));
+}
+
+fn binary(operator_type: OperatorType, a: Formula, b: Formula) -> Formula {
+    Formula::Operator(Operator { operator_type, arg1: Some(Box::new(a)), arg2: Some(Box::new(b)) })
+}
+
+fn falsum() -> Formula {
+    Formula::Operator(Operator { operator_type: OperatorType::Bottom, arg1: None, arg2: None })
+}
+
+fn is_operator(f: &Formula, op: OperatorType) -> bool {
+    matches!(f, Formula::Operator(o) if o.operator_type == op)
+}
+
+/// Arguments of a binary operator
+fn args(f: &Formula) -> (&Formula, &Formula) {
+    let Formula::Operator(o) = f else { unreachable!() };
+    (o.arg1.as_ref().unwrap(), o.arg2.as_ref().unwrap())
+}
+
+/// Replaces ¬A with A → ⊥, so that only implication has to be handled
+fn without_not(f: &Formula) -> Formula {
+    match f {
+        Formula::Operator(o) => {
+            let arg1 = o.arg1.as_ref().map(|a| without_not(a));
+            let arg2 = o.arg2.as_ref().map(|a| without_not(a));
+
+            match o.operator_type {
+                OperatorType::Not => binary(OperatorType::Impl, arg1.unwrap(), falsum()),
+                _ => Formula::Operator(Operator { operator_type: o.operator_type, arg1: arg1.map(Box::new), arg2: arg2.map(Box::new) }),
+            }
+        },
+        _ => f.clone(),
+    }
+}
+
+fn prove(mut context: Vec<Formula>, goal: Formula) -> bool {
+    // Axioms
+    if is_operator(&goal, OperatorType::Top) || context.contains(&goal) || context.iter().any(|f| is_operator(f, OperatorType::Bottom)) {
+        return true;
+    }
+
+    // Invertible rules on the right
+    if is_operator(&goal, OperatorType::And) {
+        let (a, b) = args(&goal);
+        return prove(context.clone(), a.clone()) && prove(context, b.clone());
+    }
+    if is_operator(&goal, OperatorType::Impl) {
+        let (a, b) = args(&goal);
+        context.push(a.clone());
+        return prove(context, b.clone());
+    }
+
+    // Invertible rules on the left
+    for i in 0..context.len() {
+        let Formula::Operator(o) = &context[i] else { continue };
+
+        match o.operator_type {
+            OperatorType::Top => {
+                context.remove(i);
+                return prove(context, goal);
+            },
+            OperatorType::And => {
+                let f = context.remove(i);
+                let (a, b) = args(&f);
+                context.push(a.clone());
+                context.push(b.clone());
+                return prove(context, goal);
+            },
+            OperatorType::Or => {
+                let f = context.remove(i);
+                let (a, b) = args(&f);
+                let mut other = context.clone();
+                context.push(a.clone());
+                other.push(b.clone());
+                return prove(context, goal.clone()) && prove(other, goal);
+            },
+            OperatorType::Impl => {
+                let (a, b) = args(&context[i]);
+                let (a, b) = (a.clone(), b.clone());
+
+                let replacement = match &a {
+                    // p → B with p known: B
+                    Formula::Variable(_) if context.contains(&a) => vec![b],
+                    Formula::Operator(ao) => match ao.operator_type {
+                        OperatorType::Top => vec![b],
+                        OperatorType::Bottom => vec![],
+                        // C ∧ D → B: C → (D → B)
+                        OperatorType::And => {
+                            let (c, d) = args(&a);
+                            vec![binary(OperatorType::Impl, c.clone(), binary(OperatorType::Impl, d.clone(), b))]
+                        },
+                        // C ∨ D → B: C → B, D → B
+                        OperatorType::Or => {
+                            let (c, d) = args(&a);
+                            vec![binary(OperatorType::Impl, c.clone(), b.clone()), binary(OperatorType::Impl, d.clone(), b)]
+                        },
+                        _ => continue,
+                    },
+                    _ => continue,
+                };
+
+                context.remove(i);
+                context.extend(replacement);
+                return prove(context, goal);
+            },
+            _ => (),
+        }
+    }
+
+    // Now the context only has variables and implications whose left side is an unknown variable or an implication.
+    // The remaining rules are not invertible, so all choices have to be tried.
+    if is_operator(&goal, OperatorType::Or) {
+        let (a, b) = args(&goal);
+        if prove(context.clone(), a.clone()) || prove(context.clone(), b.clone()) {
+            return true;
+        }
+    }
+
+    for i in 0..context.len() {
+        if !is_operator(&context[i], OperatorType::Impl) { continue; }
+
+        let (a, b) = args(&context[i]);
+        if !is_operator(a, OperatorType::Impl) { continue; }
+
+        // (C → D) → B: prove C → D knowing D → B, then use B
+        let (c, d) = args(a);
+        let mut left = context.clone();
+        let mut right = context.clone();
+        left[i] = binary(OperatorType::Impl, d.clone(), b.clone());
+        right[i] = b.clone();
+
+        if prove(left, binary(OperatorType::Impl, c.clone(), d.clone())) && prove(right, goal.clone()) {
+            return true;
+        }
+    }
+
+    return false;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::test_utils::*;
+
+    fn provable(before: Vec<Formula>, after: Formula) -> bool {
+        is_provable(&seq(before, after)).unwrap()
+    }
+
+    #[test]
+    fn intuitionistic_tautologies() {
+        assert!(provable(vec![a()], not(not(a()))));
+        assert!(provable(vec![], not(not(or(a(), not(a()))))));
+        assert!(provable(vec![not(or(a(), b()))], and(not(a()), not(b()))));
+        assert!(provable(vec![or(not(a()), not(b()))], not(and(a(), b()))));
+        assert!(provable(vec![imp(and(a(), b()), c())], imp(a(), imp(b(), c()))));
+        assert!(provable(vec![or(a(), b())], imp(not(a()), b())));
+        assert!(provable(vec![imp(a(), b())], imp(not(b()), not(a()))));
+        assert!(provable(vec![imp(imp(a(), b()), imp(b(), a()))], imp(b(), a())));
+        assert!(provable(vec![not(not(not(a())))], not(a())));
+        assert!(provable(vec![], top()));
+        assert!(provable(vec![bottom()], a()));
+    }
+
+    #[test]
+    fn classical_only() {
+        assert!(!provable(vec![], or(a(), not(a()))));
+        assert!(!provable(vec![not(not(a()))], a()));
+        assert!(!provable(vec![not(and(a(), b()))], or(not(a()), not(b()))));
+        assert!(!provable(vec![imp(not(a()), b())], or(a(), b())));
+        assert!(!provable(vec![imp(a(), b())], or(not(a()), b())));
+        assert!(!provable(vec![not(imp(a(), b()))], and(a(), not(b()))));
+        assert!(!provable(vec![], imp(imp(imp(a(), b()), a()), a())));
+    }
+
+    #[test]
+    fn fields_are_rejected() {
+        assert!(is_provable(&seq(vec![a()], field(0))).is_none());
+    }
+
+    #[test]
+    fn glivenko() {
+        // Γ ⊢ A is classically true if and only if Γ ⊢ ¬¬A is provable without RAA
+        let mut rng = TestRng(0x1ee7);
+
+        for _ in 0..1000 {
+            let s = random_sequent(&mut rng, 3, 3);
+            let classical = crate::proof::calcul::is_provable(&s).unwrap();
+
+            let intuitionistic = is_provable(&s).unwrap();
+            assert!(!intuitionistic || classical, "{:?} should not be provable", s);
+
+            let negated = seq(s.before.clone(), not(not(s.after[0].clone())));
+            assert_eq!(is_provable(&negated).unwrap(), classical, "{:?}", s);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+// Export of proofs to LaTeX, with the bussproofs or ebproofs package
+use super::*;
+use super::text::{Symbols, rule_text, sequent_text};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LatexStyle {
+    Bussproofs,
+    Ebproofs,
+}
+
+impl LatexStyle {
+    pub fn package_name(self) -> &'static str {
+        match self {
+            LatexStyle::Bussproofs => "bussproofs",
+            LatexStyle::Ebproofs => "ebproofs",
+        }
+    }
+}
+
+/// Code of the proof tree, in a prooftree environment. Nodes without rule are left as hypotheses.
+pub fn proof_to_latex(p: &Proof, system: &LogicSystem, style: LatexStyle) -> String {
+    let mut lines = vec![String::from("\\begin{prooftree}")];
+    add_proof_lines(p, system, style, &mut lines);
+    lines.push(String::from("\\end{prooftree}"));
+
+    return lines.join("\n") + "\n";
+}
+
+fn add_proof_lines(p: &Proof, system: &LogicSystem, style: LatexStyle, lines: &mut Vec<String>) {
+    for branch in p.branches.iter() {
+        add_proof_lines(branch, system, style, lines);
+    }
+
+    let conclusion = sequent_text(&p.root, Symbols::Latex);
+
+    match (p.rule_id, style) {
+        (None, LatexStyle::Bussproofs) => {
+            lines.push(format!("\\AxiomC{{${}$}}", conclusion));
+        },
+        (None, LatexStyle::Ebproofs) => {
+            lines.push(format!("\\hypo{{{}}}", conclusion));
+        },
+        (Some(rule_id), LatexStyle::Bussproofs) => {
+            let inference = match p.branches.len() {
+                0 => {
+                    lines.push(String::from("\\AxiomC{}"));
+                    "UnaryInfC"
+                },
+                1 => "UnaryInfC",
+                2 => "BinaryInfC",
+                3 => "TrinaryInfC",
+                4 => "QuaternaryInfC",
+                _ => "QuinaryInfC",
+            };
+
+            lines.push(format!("\\RightLabel{{\\scriptsize {}}}", rule_text(system.rules[rule_id as usize].display_text(), Symbols::Latex)));
+            lines.push(format!("\\{}{{${}$}}", inference, conclusion));
+        },
+        (Some(rule_id), LatexStyle::Ebproofs) => {
+            lines.push(format!("\\infer{}[{}]{{{}}}", p.branches.len(), rule_text(system.rules[rule_id as usize].display_text(), Symbols::Latex), conclusion));
+        },
+    }
+}
+
+/// Whole document with the proof, that can be compiled as is
+pub fn latex_document(p: &Proof, system: &LogicSystem, style: LatexStyle) -> String {
+    return format!(
+        "\\documentclass{{article}}\n\\usepackage{{{}}}\n\n\\begin{{document}}\n\n{}\n\\end{{document}}\n",
+        style.package_name(),
+        proof_to_latex(p, system, style)
+    );
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::test_utils::*;
+    use crate::proof::text::formula_text;
+
+    /// A ∧ B ⊢ B ∧ A
+    fn commutation_proof() -> Proof {
+        let root = seq(vec![and(a(), b())], and(b(), a()));
+
+        numbered(tree(root.clone(), "∧i", vec![
+            tree(seq(vec![and(a(), b())], b()), "∧e", vec![
+                tree(seq(vec![and(a(), b())], and(a(), b())), "Ax", vec![]),
+            ]),
+            node(seq(vec![and(a(), b())], a()), vec![]),
+        ]))
+    }
+
+    #[test]
+    fn formulas() {
+        assert_eq!(formula_text(&imp(and(a(), b()), c()), &[], Symbols::Latex), "A \\land B \\to C");
+        assert_eq!(formula_text(&and(imp(a(), b()), c()), &[], Symbols::Latex), "(A \\to B) \\land C");
+        assert_eq!(formula_text(&not(or(a(), bottom())), &[], Symbols::Latex), "\\lnot (A \\lor \\bot)");
+        assert_eq!(formula_text(&not(not(a())), &[], Symbols::Latex), "\\lnot (\\lnot A)");
+        assert_eq!(formula_text(&or(field(0), top()), &[], Symbols::Latex), "\\_ \\lor \\top");
+        assert_eq!(sequent_text(&seq(vec![], a()), Symbols::Latex), "\\vdash A");
+        assert_eq!(sequent_text(&seq(vec![a(), b()], a()), Symbols::Latex), "A, B \\vdash A");
+
+        let named = Sequent { variables: vec![String::from("rain"), String::from("P")], ..seq(vec![imp(a(), b())], c()) };
+        assert_eq!(sequent_text(&named, Symbols::Latex), "\\mathit{rain} \\to P \\vdash A");
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(rule_text("→i", Symbols::Latex), "\\ensuremath{\\to}\\textrm{i}");
+        assert_eq!(rule_text("RAA", Symbols::Latex), "\\textrm{RAA}");
+    }
+
+    #[test]
+    fn bussproofs() {
+        let res = proof_to_latex(&commutation_proof(), &natural_logic::get_system(), LatexStyle::Bussproofs);
+
+        assert_eq!(res, [
+            "\\begin{prooftree}",
+            "\\AxiomC{}",
+            "\\RightLabel{\\scriptsize \\textrm{Ax}}",
+            "\\UnaryInfC{$A \\land B \\vdash A \\land B$}",
+            "\\RightLabel{\\scriptsize \\ensuremath{\\land}\\textrm{e}}",
+            "\\UnaryInfC{$A \\land B \\vdash B$}",
+            "\\AxiomC{$A \\land B \\vdash A$}",
+            "\\RightLabel{\\scriptsize \\ensuremath{\\land}\\textrm{i}}",
+            "\\BinaryInfC{$A \\land B \\vdash B \\land A$}",
+            "\\end{prooftree}",
+            "",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn ebproofs() {
+        let res = proof_to_latex(&commutation_proof(), &natural_logic::get_system(), LatexStyle::Ebproofs);
+
+        assert_eq!(res, [
+            "\\begin{prooftree}",
+            "\\infer0[\\textrm{Ax}]{A \\land B \\vdash A \\land B}",
+            "\\infer1[\\ensuremath{\\land}\\textrm{e}]{A \\land B \\vdash B}",
+            "\\hypo{A \\land B \\vdash A}",
+            "\\infer2[\\ensuremath{\\land}\\textrm{i}]{A \\land B \\vdash B \\land A}",
+            "\\end{prooftree}",
+            "",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn document() {
+        let res = latex_document(&commutation_proof(), &natural_logic::get_system(), LatexStyle::Ebproofs);
+        assert!(res.starts_with("\\documentclass{article}\n\\usepackage{ebproofs}\n"));
+        assert!(res.contains("\\begin{prooftree}"));
+        assert!(res.ends_with("\\end{document}\n"));
+    }
+}
@@ -0,0 +1,110 @@
+// Layout of proof trees: sizes of the elements, in screen units.
+// Shared by the screen rendering and the SVG export, so that both look the same.
+use std::collections::HashMap;
+
+use super::*;
+
+// Screen units
+pub const PROOF_MARGIN: f32 = 200e-3;
+pub const SEQUENT_MARGIN: f32 = 30e-3;
+pub const COMMA_MARGIN: f32 = 10e-3;
+pub const FIELD_WIDTH: f32 = 55e-3;
+pub const FIELD_HEIGHT: f32 = 70e-3;
+pub const FIELD_Y_SHIFT: f32 = 3e-3;
+pub const TEXT_SCALE: f32 = 50.0;
+pub const LINE_HEIGHT: f32 = 120e-3;
+pub const BAR_HEIGHT: f32 = 5e-3;
+pub const PAR_POSITION: f32 = 100e-3;
+pub const OPERATOR_MARGIN: f32 = 10e-3;
+pub const VARIABLE_Y_SHIFT: f32 = -7e-3;
+
+pub const RULE_MARGIN: f32 = 10e-3;
+pub const RULE_TEXT_SCALE: f32 = 0.5; // 1 is normal text
+
+/// What the layout needs to know about the place where the proof is drawn
+pub trait LayoutInfo {
+    /// Width of each character at TEXT_SCALE, as computed by crate::rendering::compute_char_sizes
+    fn char_widths(&self) -> &HashMap<char, f32>;
+    fn scale(&self) -> f32;
+    /// Horizontal scale of a node created at this time, for its appear animation
+    fn appear_scale(&mut self, creation_time: f32) -> f32;
+    /// Width of a field relative to FIELD_WIDTH, for its appear animation
+    fn field_scale(&mut self, field_id: u32) -> f32;
+}
+
+
+pub fn get_proof_width(p: &Proof, info: &mut impl LayoutInfo) -> f32 {
+    let x_scale = info.appear_scale(p.creation_time);
+    return f32::max(get_proof_branches_width(p, info), get_sequent_width(&p.root, info)) * x_scale;
+}
+
+
+pub fn get_proof_branches_width(p: &Proof, info: &mut impl LayoutInfo) -> f32 {
+    let mut sum = if p.branches.len() > 0 { (p.branches.len() - 1) as f32 * PROOF_MARGIN * info.scale() } else { 0.0 };
+
+    for proof in p.branches.iter() {
+        sum += get_proof_width(proof, info);
+    }
+
+    return sum;
+}
+
+pub fn get_proof_root_width(p: &Proof, info: &mut impl LayoutInfo) -> f32 {
+    let x_scale = info.appear_scale(p.creation_time);
+    return get_sequent_width(&p.root, info) * x_scale;
+}
+
+pub fn get_sequent_width(s: &Sequent, info: &mut impl LayoutInfo) -> f32 {
+    let mut sum = get_character_width('⊢', info);
+
+    if s.before.len() > 0 { sum += SEQUENT_MARGIN * info.scale() };
+    if s.after.len() > 0 { sum += SEQUENT_MARGIN * info.scale() };
+
+    let comma_size = COMMA_MARGIN + get_character_width(',', info);
+    if s.before.len() > 0 { sum += (s.before.len() as f32 - 1.0) * comma_size };
+    if s.before.len() > 0 { sum += (s.after.len() as f32 - 1.0) * comma_size };
+
+    for f in s.before.iter().chain(s.after.iter()) {
+        sum += get_formula_width(f, &s.variables, info);
+    }
+
+    return sum;
+}
+
+
+/// variables are the names of the sequent's variables
+pub fn get_formula_width(f: &Formula, variables: &[String], info: &mut impl LayoutInfo) -> f32 {
+    match f {
+        Formula::Operator(operator) => {
+            let mut sum = get_character_width(get_operator_symbol(operator.operator_type).chars().next().unwrap(), info);
+            sum += get_operator_arity(operator.operator_type) as f32 * OPERATOR_MARGIN * info.scale();
+
+            let priority = get_operator_priority(operator.operator_type);
+            let parentheses_width = get_character_width('(', info) + get_character_width(')', info) ;
+
+            for arg in operator.arg1.iter().chain(operator.arg2.iter()) {
+                if needs_parentheses(priority, arg) {
+                    sum += parentheses_width;
+                }
+
+                sum += get_formula_width(arg, variables, info);
+            }
+            
+            return sum;
+        },
+        Formula::Variable(id) => {
+            return variable_name(variables, *id).chars().map(|c| get_character_width(c, info)).sum();
+        },
+        Formula::NotCompleted(field_info) => {
+            return FIELD_WIDTH * info.scale() * info.field_scale(field_info.id);
+        },
+    }
+}
+
+
+pub fn get_character_width(char: char, info: &impl LayoutInfo) -> f32 {    
+    match info.char_widths().get(&char) {
+        Some(w) => *w / 1080.0 * 2.0 * info.scale(), // Why 1080.0? No one knows...
+        None => panic!("Unknown char width. Add it to SYMBOLS constant!"),
+    }
+}
@@ -1,63 +1,103 @@
 
-pub mod rendering;
+pub mod layout;
 pub mod calcul;
 pub mod natural_logic;
+pub mod truth_table;
+pub mod intuitionistic;
+pub mod prover;
+pub mod latex;
+pub mod svg;
+pub mod text;
 
-type Variable = u32;
+#[cfg(test)]
+pub mod test_utils;
+
+use std::collections::HashMap;
+
+pub type Variable = u32;
+
+/// Truth value of some variables
+pub type Valuation = HashMap<Variable, bool>;
 
 pub const MAX_VARIABLE_COUNT: u32 = 10;
 
+/// Letters used for variables, in order
+pub const VARIABLE_LETTERS: &str = "ABCDEFGHIJK";
+
+/// Name of the variable in the symbol table of a sequent. Variables missing from the table get the first letters
+/// that aren't already names.
+pub fn variable_name(variables: &[String], id: Variable) -> String {
+    if let Some(name) = variables.get(id as usize) {
+        return name.clone();
+    }
+
+    return VARIABLE_LETTERS.chars()
+        .map(String::from)
+        .filter(|letter| !variables.contains(letter))
+        .nth(id as usize - variables.len())
+        .unwrap_or_else(|| format!("X{}", id));
+}
+
 /// Each rule will be a dedicated type that implement this.
 pub trait Rule {
     /// Create proof template from the sequent. Returns None if not compatible. Also returns the number of created empty fields
     /// next_field_id should be increased if new empty fields are created. Otherwise, it must not be modified.
     fn create_branches(&self, root: &Sequent) -> (Option<Vec<Sequent>>, u32); 
-    /// Check if the sequents above the root of the proofs corresponds to the rule.
-    /// 
-    /// TODO: Currently the verification is NOT implemented 
+    /// Check if the sequents above the root of the proofs corresponds to the rule, once all the fields are completed.
     fn check_validity(&self, proof: &Proof) -> bool; 
     /// Text to be displayed to the right of the horizontal bar.
     fn display_text(&self) -> &'static str; 
 }
 
 /// A proof tree.
-#[derive(Clone)]
+/// 
+/// Times are only used for animations, so they are not saved.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Proof {
     pub id: u32,
     pub root: Sequent,
     pub branches: Vec<Proof>,
     pub rule_id: Option<u32>,
     
+    #[serde(skip)]
     pub last_focused_time: f32,
+    #[serde(skip)]
     pub creation_time: f32,
+    #[serde(skip)]
     pub rule_set_time: f32,
     pub is_rule_invalid: bool,
+    /// Set when the sequent has been found impossible to prove
+    pub is_unprovable: bool,
 }
 
 /// A sequent!
 /// 
 /// I used vec for both sides, will be useful if we want to implement other logic systems.
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Sequent {
     pub before: Vec<Formula>,
     pub after: Vec<Formula>,
+    /// Name of each variable, indexed by its id. Rules copy the sequent, so every node of a proof has the names of the level.
+    /// Variables without a name use the letters of VARIABLE_LETTERS.
+    #[serde(default)]
+    pub variables: Vec<String>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum OperatorType {
     Not, Impl, And, Or, Top, Bottom
 }
 
 /// arg1 and arg2 are None if the arity is 0 or 1.
 /// Non-variable constants (top, bottom) are operators with arity 0.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Operator {
     pub operator_type: OperatorType,
     pub arg1: Option<Box<Formula>>,
     pub arg2: Option<Box<Formula>>
 }
 
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
 pub enum Formula {
     Operator(Operator),
     Variable(Variable),
@@ -67,20 +107,42 @@ pub enum Formula {
     NotCompleted(FormulaField),
 }
 
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
 pub struct FormulaField {
     pub id: u32,
     pub next_id: u32,
     pub prev_id: u32,
 }
 
+/// A node of a proof that is not correct
+#[derive(Clone, Debug)]
+pub struct VerificationError {
+    pub node_id: u32,
+    pub message: String,
+}
+
 pub struct LogicSystem {
     pub operators: Vec<OperatorType>,
     pub rules: Vec<Box<dyn Rule>>,
     pub special_rules: Vec<Option<Box<dyn Rule>>>,
 }
 
-fn get_operator_arity(op: OperatorType) -> u32 {
+/// Rules a level lets the player use, named by their display text like "→i" or "RAA"
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct RuleFilter {
+    /// None if every rule is allowed
+    pub allowed: Option<Vec<String>>,
+    pub banned: Vec<String>,
+}
+
+impl RuleFilter {
+    pub fn allows(&self, rule_name: &str) -> bool {
+        let allowed = self.allowed.as_ref().is_none_or(|allowed| allowed.iter().any(|r| r == rule_name));
+        return allowed && !self.banned.iter().any(|r| r == rule_name);
+    }
+}
+
+pub fn get_operator_arity(op: OperatorType) -> u32 {
     match op {
         OperatorType::Not => 1,
         OperatorType::Impl => 2,
@@ -103,7 +165,7 @@ pub fn get_operator_symbol(op: OperatorType) -> &'static str {
 }
 
 /// Smaller number means more priority. Use 0 for constants.
-fn get_operator_priority(op: OperatorType) -> f32 {
+pub fn get_operator_priority(op: OperatorType) -> f32 {
     match op {
         OperatorType::Not => 1.0,
         OperatorType::Impl => 3.0,
@@ -114,6 +176,17 @@ fn get_operator_priority(op: OperatorType) -> f32 {
     }
 }
 
+/// Should f be written between parentheses, as an argument of an operator with this priority?
+pub fn needs_parentheses(parent_priority: f32, f: &Formula) -> bool {
+    match f {
+        Formula::Operator(operator) => {
+            get_operator_priority(operator.operator_type) >= parent_priority
+        },
+        Formula::Variable(_) => false,
+        Formula::NotCompleted(_) => false,
+    }
+}
+
 /// Create a variable, then places it in field with field_id. Returns the id of the next field to be focused, if there is any left. 
 pub fn place_variable(var: Variable, field_id: u32, proof: &mut Proof) -> Option<u32> {
     let mut fields = search_fields_by_id_in_proof(proof, Some(field_id));
@@ -287,12 +360,12 @@ fn search_field_id_in_sequent<'a>(s: &'a mut Sequent, index: Option<u32>, res: &
 fn search_field_id_in_formula<'a>(f: &'a mut Formula, index: Option<u32>, res: &mut Vec<&'a mut Formula>) {
     match f {
         Formula::Operator(operator) => {
-            if operator.arg1.is_some() {
-                search_field_id_in_formula(operator.arg1.as_mut().unwrap(), index, res);
+            if let Some(arg) = operator.arg1.as_mut() {
+                search_field_id_in_formula(arg, index, res);
             }
 
-            if operator.arg2.is_some() {
-                search_field_id_in_formula(operator.arg2.as_mut().unwrap(), index, res);
+            if let Some(arg) = operator.arg2.as_mut() {
+                search_field_id_in_formula(arg, index, res);
             }
         },
         Formula::Variable(_) => (),
@@ -332,16 +405,15 @@ pub fn sequent_as_empty_proof(s: Sequent, time: f32, next_id: &mut u32) -> Proof
         creation_time: time,
         rule_set_time: f32::NEG_INFINITY,
         is_rule_invalid: false,
+        is_unprovable: false,
     };
 }
 
 pub fn execute_on_first_operator_of_type<T>(formulas: &[Formula], op_type: OperatorType, f: &dyn Fn(usize, &Option<Box<Formula>>, &Option<Box<Formula>>) -> T, otherwise: T) -> T {
     for (i, formula) in formulas.iter().enumerate() {
         match formula {
-            super::Formula::Operator(op) => {
-                if op.operator_type == op_type {
-                    return f(i, &op.arg1, &op.arg2);
-                }
+            super::Formula::Operator(op) if op.operator_type == op_type => {
+                return f(i, &op.arg1, &op.arg2);
             },
             _ => ()
         }
@@ -361,5 +433,203 @@ pub fn get_empty_sequent() -> Sequent {
                 next_id: 0,
             }),
         ],
+        variables: vec![],
     };
 }
+
+/// True if the formula does not contain any field to be completed
+pub fn is_formula_completed(f: &Formula) -> bool {
+    match f {
+        Formula::Operator(operator) => {
+            operator.arg1.as_ref().is_none_or(|f| is_formula_completed(f))
+            && operator.arg2.as_ref().is_none_or(|f| is_formula_completed(f))
+        },
+        Formula::Variable(_) => true,
+        Formula::NotCompleted(_) => false,
+    }
+}
+
+pub fn is_sequent_completed(s: &Sequent) -> bool {
+    s.before.iter().chain(s.after.iter()).all(is_formula_completed)
+}
+
+/// Number of rules applied in the proof
+pub fn count_rules(p: &Proof) -> u32 {
+    return p.rule_id.is_some() as u32 + p.branches.iter().map(count_rules).sum::<u32>();
+}
+
+pub fn uses_rule(p: &Proof, rule_id: u32) -> bool {
+    return p.rule_id == Some(rule_id) || p.branches.iter().any(|b| uses_rule(b, rule_id));
+}
+
+/// Applies the rule to the root of the proof again, and checks that the branches of the proof are the created ones once completed. 
+/// Each field of the created branches can be completed by any formula, but all fields with the same id must contain the same formula.
+/// Returns the formulas used to complete the fields, indexed by field id, or None if the branches don't match.
+pub fn match_rule_branches(rule: &dyn Rule, proof: &Proof) -> Option<HashMap<u32, Formula>> {
+    let (Some(templates), _) = rule.create_branches(&proof.root) else { return None; };
+
+    if templates.len() != proof.branches.len() { return None; }
+
+    let mut fields = HashMap::new();
+
+    for (template, branch) in templates.iter().zip(proof.branches.iter()) {
+        if !match_sequent(template, &branch.root, &mut fields) {
+            return None;
+        }
+    }
+
+    return Some(fields);
+}
+
+fn match_sequent(template: &Sequent, actual: &Sequent, fields: &mut HashMap<u32, Formula>) -> bool {
+    if template.before.len() != actual.before.len() || template.after.len() != actual.after.len() {
+        return false;
+    }
+
+    return template.before.iter().zip(actual.before.iter())
+        .chain(template.after.iter().zip(actual.after.iter()))
+        .all(|(t, a)| match_formula(t, a, fields));
+}
+
+fn match_formula(template: &Formula, actual: &Formula, fields: &mut HashMap<u32, Formula>) -> bool {
+    match (template, actual) {
+        (Formula::NotCompleted(field), _) => {
+            if !is_formula_completed(actual) {
+                return false;
+            }
+
+            match fields.get(&field.id) {
+                Some(f) => f == actual,
+                None => {
+                    fields.insert(field.id, actual.clone());
+                    true
+                },
+            }
+        },
+        (Formula::Operator(t), Formula::Operator(a)) => {
+            if t.operator_type != a.operator_type {
+                return false;
+            }
+
+            let match_arg = |t: &Option<Box<Formula>>, a: &Option<Box<Formula>>, fields: &mut HashMap<u32, Formula>| {
+                match (t, a) {
+                    (Some(t), Some(a)) => match_formula(t, a, fields),
+                    (None, None) => true,
+                    _ => false,
+                }
+            };
+
+            return match_arg(&t.arg1, &a.arg1, fields) && match_arg(&t.arg2, &a.arg2, fields);
+        },
+        (Formula::Variable(t), Formula::Variable(a)) => t == a,
+        _ => false,
+    }
+}
+
+/// Checks the whole proof from scratch: every node must be finished, completed, and its premises must be the ones given by its rule.
+/// Returns all nodes that are not correct.
+pub fn verify_proof(proof: &Proof, system: &LogicSystem) -> Result<(), Vec<VerificationError>> {
+    let mut errors = Vec::new();
+    verify_proof_node(proof, system, &mut errors);
+
+    if errors.is_empty() {
+        return Ok(());
+    }
+    else {
+        return Err(errors);
+    }
+}
+
+fn verify_proof_node(proof: &Proof, system: &LogicSystem, errors: &mut Vec<VerificationError>) {
+    let mut error = |message: String| errors.push(VerificationError { node_id: proof.id, message });
+
+    match proof.rule_id {
+        None => error(String::from("No rule applied")),
+        Some(rule_id) => {
+            match system.rules.get(rule_id as usize) {
+                None => error(format!("Unknown rule {}", rule_id)),
+                Some(rule) => {
+                    if !is_sequent_completed(&proof.root) {
+                        error(String::from("Sequent is not completed"));
+                    }
+                    else if !rule.check_validity(proof) {
+                        error(format!("Premises do not match rule {}", rule.display_text()));
+                    }
+                },
+            }
+        },
+    }
+
+    for b in proof.branches.iter() {
+        verify_proof_node(b, system, errors);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::*;
+
+    /// A ⊢ ¬¬A
+    fn double_negation_proof() -> Proof {
+        let ctx = vec![a(), not(a())];
+
+        numbered(tree(seq(vec![a()], not(not(a()))), "¬i", vec![
+            tree(seq(ctx.clone(), bottom()), "¬e", vec![
+                tree(seq(ctx.clone(), a()), "Ax", vec![]),
+                tree(seq(ctx.clone(), not(a())), "Ax", vec![]),
+            ]),
+        ]))
+    }
+
+    #[test]
+    fn verify_correct_proof() {
+        assert!(verify_proof(&double_negation_proof(), &natural_logic::get_system()).is_ok());
+    }
+
+    #[test]
+    fn verify_reports_every_wrong_node() {
+        let mut p = double_negation_proof();
+
+        // Wrong formula in the second premise of ¬e, which also breaks its axiom
+        p.branches[0].branches[1].root.after[0] = not(b());
+        // Unfinished node
+        p.branches[0].branches[0].rule_id = None;
+
+        let errors = verify_proof(&p, &natural_logic::get_system()).unwrap_err();
+        let mut ids: Vec<u32> = errors.iter().map(|e| e.node_id).collect();
+        ids.sort();
+
+        assert_eq!(ids, vec![
+            p.branches[0].id,
+            p.branches[0].branches[0].id,
+            p.branches[0].branches[1].id,
+        ]);
+    }
+
+    #[test]
+    fn verify_rejects_uncompleted_fields() {
+        let mut p = double_negation_proof();
+        p.branches[0].branches[1].root.after[0] = not(field(0));
+
+        assert!(verify_proof(&p, &natural_logic::get_system()).is_err());
+    }
+
+    #[test]
+    fn save_and_load() {
+        let mut p = double_negation_proof();
+        p.branches[0].branches[1].root.after[0] = not(field(3));
+        p.branches[0].branches[1].rule_id = None;
+        p.branches[0].is_rule_invalid = true;
+        p.creation_time = 12.0;
+
+        let text = ron::to_string(&p).unwrap();
+        let loaded: Proof = ron::from_str(&text).unwrap();
+
+        assert_eq!(ron::to_string(&loaded).unwrap(), text);
+        assert_eq!(loaded.branches[0].branches[1].root, p.branches[0].branches[1].root);
+        assert!(loaded.branches[0].is_rule_invalid);
+        assert_eq!(loaded.creation_time, 0.0);
+    }
+}
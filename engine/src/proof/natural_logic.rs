@@ -1,5 +1,19 @@
 use super::*;
 
+// Index of each rule in the system. Must match the order in get_system().
+pub const NOT_I: u32 = 0;
+pub const NOT_E: u32 = 1;
+pub const IMPL_I: u32 = 2;
+pub const IMPL_E: u32 = 3;
+pub const AND_I: u32 = 4;
+pub const AND_E: u32 = 5;
+pub const OR_I: u32 = 6;
+pub const OR_E: u32 = 7;
+pub const TOP_I: u32 = 8;
+pub const BOTTOM_E: u32 = 9;
+pub const RAA_RULE: u32 = 10;
+pub const AXIOM: u32 = 11;
+
 
 pub fn get_system() -> LogicSystem {
     return LogicSystem {
@@ -59,8 +73,8 @@ impl Rule for ImplI {
         }, (None, 0));
     }
 
-    fn check_validity(&self, _proof: &Proof) -> bool {
-        true
+    fn check_validity(&self, proof: &Proof) -> bool {
+        match_rule_branches(self, proof).is_some()
     }
 
     fn display_text(&self) -> &'static str {
@@ -104,8 +118,8 @@ impl Rule for ImplE {
         ]), 1);
     }
 
-    fn check_validity(&self, _proof: &Proof) -> bool {
-        true
+    fn check_validity(&self, proof: &Proof) -> bool {
+        match_rule_branches(self, proof).is_some()
     }
 
     fn display_text(&self) -> &'static str {
@@ -143,14 +157,10 @@ impl Rule for AndE {
     }
 
     fn check_validity(&self, proof: &Proof) -> bool {
-        if proof.branches.len() != 1 { return false; }
+        let Some(fields) = match_rule_branches(self, proof) else { return false; };
 
-        let Formula::Operator(op) = &proof.branches[0].root.after[0] else { return false; };
-
-        if op.operator_type != OperatorType::And { return false; }
-
-        return proof.root.after[0] == *op.arg1.as_ref().unwrap().as_ref()
-            || proof.root.after[0] == *op.arg2.as_ref().unwrap().as_ref();
+        // One side of the conjunction must be the conclusion
+        return proof.root.after[0] == fields[&0] || proof.root.after[0] == fields[&1];
     }
 
     fn display_text(&self) -> &'static str {
@@ -178,14 +188,12 @@ impl Rule for OrI {
     }
 
     fn check_validity(&self, proof: &Proof) -> bool {
-        if proof.branches.len() != 1 { return false; }
-
-        let Formula::Operator(op) = &proof.root.after[0] else { unreachable!(); };
+        let Some(fields) = match_rule_branches(self, proof) else { return false; };
 
-        if op.operator_type != OperatorType::Or { unreachable!(); }
-
-        return *op.arg1.as_ref().unwrap().as_ref() == proof.branches[0].root.after[0]
-            || *op.arg2.as_ref().unwrap().as_ref() == proof.branches[0].root.after[0];
+        // The premise must be one side of the disjunction
+        return execute_on_first_operator_of_type(&proof.root.after, OperatorType::Or, &|_, arg1, arg2| {
+            fields[&0] == **arg1.as_ref().unwrap() || fields[&0] == **arg2.as_ref().unwrap()
+        }, false);
     }
 
     fn display_text(&self) -> &'static str {
@@ -335,8 +343,8 @@ impl Rule for AndI {
         }, (None, 0));
     }
 
-    fn check_validity(&self, _proof: &Proof) -> bool {
-        true
+    fn check_validity(&self, proof: &Proof) -> bool {
+        match_rule_branches(self, proof).is_some()
     }
 
     fn display_text(&self) -> &'static str {
@@ -365,8 +373,8 @@ impl Rule for NotI {
         }, (None, 0));
     }
 
-    fn check_validity(&self, _proof: &Proof) -> bool {
-        true
+    fn check_validity(&self, proof: &Proof) -> bool {
+        match_rule_branches(self, proof).is_some()
     }
 
     fn display_text(&self) -> &'static str {
@@ -408,8 +416,8 @@ impl Rule for NotE {
         }, (None, 0));
     }
 
-    fn check_validity(&self, _proof: &Proof) -> bool {
-        true
+    fn check_validity(&self, proof: &Proof) -> bool {
+        match_rule_branches(self, proof).is_some()
     }
 
     fn display_text(&self) -> &'static str {
@@ -431,8 +439,8 @@ impl Rule for Axiom {
         }
     }
 
-    fn check_validity(&self, _proof: &Proof) -> bool {
-        true
+    fn check_validity(&self, proof: &Proof) -> bool {
+        match_rule_branches(self, proof).is_some()
     }
 
     fn display_text(&self) -> &'static str {
@@ -480,8 +488,8 @@ impl Rule for OrE {
         return (Some(vec![a, b, c]), 2)
     }
 
-    fn check_validity(&self, _proof: &Proof) -> bool {
-        true
+    fn check_validity(&self, proof: &Proof) -> bool {
+        match_rule_branches(self, proof).is_some()
     }
 
     fn display_text(&self) -> &'static str {
@@ -499,8 +507,8 @@ impl Rule for TopI {
         }, (None, 0));
     }
 
-    fn check_validity(&self, _proof: &Proof) -> bool {
-        true
+    fn check_validity(&self, proof: &Proof) -> bool {
+        match_rule_branches(self, proof).is_some()
     }
 
     fn display_text(&self) -> &'static str {
@@ -521,8 +529,8 @@ impl Rule for BottomE {
         return (Some(vec![s]), 0);
     }
 
-    fn check_validity(&self, _proof: &Proof) -> bool {
-        true
+    fn check_validity(&self, proof: &Proof) -> bool {
+        match_rule_branches(self, proof).is_some()
     }
 
     fn display_text(&self) -> &'static str {
@@ -544,8 +552,8 @@ impl Rule for RAA {
         return (Some(vec![s]), 0);
     }
 
-    fn check_validity(&self, _proof: &Proof) -> bool {
-        true
+    fn check_validity(&self, proof: &Proof) -> bool {
+        match_rule_branches(self, proof).is_some()
     }
 
     fn display_text(&self) -> &'static str {
@@ -553,3 +561,236 @@ impl Rule for RAA {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::test_utils::*;
+
+    #[test]
+    fn impl_i() {
+        let root = seq(vec![c()], imp(a(), b()));
+        assert!(ImplI {}.check_validity(&node(root.clone(), vec![seq(vec![a(), c()], b())])));
+
+        assert!(!ImplI {}.check_validity(&node(root.clone(), vec![seq(vec![c()], b())])));
+        assert!(!ImplI {}.check_validity(&node(root.clone(), vec![seq(vec![b(), c()], a())])));
+        assert!(!ImplI {}.check_validity(&node(root, vec![])));
+    }
+
+    #[test]
+    fn impl_e() {
+        let root = seq(vec![c()], b());
+        assert!(ImplE {}.check_validity(&node(root.clone(), vec![seq(vec![c()], imp(a(), b())), seq(vec![c()], a())])));
+        assert!(ImplE {}.check_validity(&node(root.clone(), vec![seq(vec![c()], imp(not(c()), b())), seq(vec![c()], not(c()))])));
+
+        // The antecedent must be the same in both premises
+        assert!(!ImplE {}.check_validity(&node(root.clone(), vec![seq(vec![c()], imp(a(), b())), seq(vec![c()], c())])));
+        // The consequent must be the conclusion
+        assert!(!ImplE {}.check_validity(&node(root.clone(), vec![seq(vec![c()], imp(a(), c())), seq(vec![c()], a())])));
+        // The context can't change
+        assert!(!ImplE {}.check_validity(&node(root.clone(), vec![seq(vec![], imp(a(), b())), seq(vec![c()], a())])));
+        // Fields must be completed
+        assert!(!ImplE {}.check_validity(&node(root, vec![seq(vec![c()], imp(field(0), b())), seq(vec![c()], field(0))])));
+    }
+
+    #[test]
+    fn and_i() {
+        let root = seq(vec![c()], and(a(), b()));
+        assert!(AndI {}.check_validity(&node(root.clone(), vec![seq(vec![c()], a()), seq(vec![c()], b())])));
+
+        assert!(!AndI {}.check_validity(&node(root.clone(), vec![seq(vec![c()], b()), seq(vec![c()], a())])));
+        assert!(!AndI {}.check_validity(&node(root, vec![seq(vec![c()], a())])));
+    }
+
+    #[test]
+    fn and_e() {
+        let root = seq(vec![c()], a());
+        assert!(AndE {}.check_validity(&node(root.clone(), vec![seq(vec![c()], and(a(), b()))])));
+        assert!(AndE {}.check_validity(&node(root.clone(), vec![seq(vec![c()], and(b(), a()))])));
+
+        assert!(!AndE {}.check_validity(&node(root.clone(), vec![seq(vec![c()], and(b(), c()))])));
+        assert!(!AndE {}.check_validity(&node(root.clone(), vec![seq(vec![c()], or(a(), b()))])));
+        assert!(!AndE {}.check_validity(&node(root, vec![seq(vec![], and(a(), b()))])));
+    }
+
+    #[test]
+    fn or_i() {
+        let root = seq(vec![c()], or(a(), b()));
+        assert!(OrI {}.check_validity(&node(root.clone(), vec![seq(vec![c()], a())])));
+        assert!(OrI {}.check_validity(&node(root.clone(), vec![seq(vec![c()], b())])));
+
+        assert!(!OrI {}.check_validity(&node(root.clone(), vec![seq(vec![c()], c())])));
+        assert!(!OrI {}.check_validity(&node(root.clone(), vec![seq(vec![c()], or(a(), b()))])));
+        assert!(!OrI {}.check_validity(&node(root, vec![seq(vec![a()], a())])));
+    }
+
+    #[test]
+    fn or_e() {
+        let root = seq(vec![c()], b());
+        let ok = vec![
+            seq(vec![c(), a()], b()),
+            seq(vec![c(), not(a())], b()),
+            seq(vec![c()], or(a(), not(a()))),
+        ];
+        assert!(OrE {}.check_validity(&node(root.clone(), ok)));
+
+        // The cases must be the sides of the disjunction
+        let swapped = vec![
+            seq(vec![c(), not(a())], b()),
+            seq(vec![c(), a()], b()),
+            seq(vec![c()], or(a(), not(a()))),
+        ];
+        assert!(!OrE {}.check_validity(&node(root.clone(), swapped)));
+
+        let wrong_conclusion = vec![
+            seq(vec![c(), a()], c()),
+            seq(vec![c(), not(a())], b()),
+            seq(vec![c()], or(a(), not(a()))),
+        ];
+        assert!(!OrE {}.check_validity(&node(root.clone(), wrong_conclusion)));
+
+        let not_or = vec![
+            seq(vec![c(), a()], b()),
+            seq(vec![c(), not(a())], b()),
+            seq(vec![c()], and(a(), not(a()))),
+        ];
+        assert!(!OrE {}.check_validity(&node(root, not_or)));
+    }
+
+    #[test]
+    fn not_i() {
+        let root = seq(vec![c()], not(a()));
+        assert!(NotI {}.check_validity(&node(root.clone(), vec![seq(vec![c(), a()], bottom())])));
+
+        assert!(!NotI {}.check_validity(&node(root.clone(), vec![seq(vec![c()], bottom())])));
+        assert!(!NotI {}.check_validity(&node(root, vec![seq(vec![c(), a()], a())])));
+    }
+
+    #[test]
+    fn not_e() {
+        let root = seq(vec![c()], bottom());
+        assert!(NotE {}.check_validity(&node(root.clone(), vec![seq(vec![c()], a()), seq(vec![c()], not(a()))])));
+
+        assert!(!NotE {}.check_validity(&node(root.clone(), vec![seq(vec![c()], a()), seq(vec![c()], not(b()))])));
+        assert!(!NotE {}.check_validity(&node(root.clone(), vec![seq(vec![c()], not(a())), seq(vec![c()], a())])));
+        assert!(!NotE {}.check_validity(&node(seq(vec![c()], a()), vec![seq(vec![c()], a()), seq(vec![c()], not(a()))])));
+    }
+
+    #[test]
+    fn top_i() {
+        assert!(TopI {}.check_validity(&node(seq(vec![], top()), vec![])));
+
+        assert!(!TopI {}.check_validity(&node(seq(vec![], a()), vec![])));
+        assert!(!TopI {}.check_validity(&node(seq(vec![], top()), vec![seq(vec![], top())])));
+    }
+
+    #[test]
+    fn bottom_e() {
+        let root = seq(vec![c()], a());
+        assert!(BottomE {}.check_validity(&node(root.clone(), vec![seq(vec![c()], bottom())])));
+
+        assert!(!BottomE {}.check_validity(&node(root.clone(), vec![seq(vec![c()], a())])));
+        assert!(!BottomE {}.check_validity(&node(root, vec![seq(vec![], bottom())])));
+    }
+
+    #[test]
+    fn raa() {
+        let root = seq(vec![c()], a());
+        assert!(RAA {}.check_validity(&node(root.clone(), vec![seq(vec![c(), not(a())], bottom())])));
+
+        assert!(!RAA {}.check_validity(&node(root.clone(), vec![seq(vec![c(), a()], bottom())])));
+        assert!(!RAA {}.check_validity(&node(root, vec![seq(vec![c(), not(a())], a())])));
+    }
+
+    #[test]
+    fn axiom() {
+        assert!(Axiom {}.check_validity(&node(seq(vec![b(), a()], a()), vec![])));
+
+        assert!(!Axiom {}.check_validity(&node(seq(vec![b()], a()), vec![])));
+        assert!(!Axiom {}.check_validity(&node(seq(vec![a()], a()), vec![seq(vec![a()], a())])));
+    }
+
+    /// Field created with another one, that focus goes to next
+    fn linked_field(id: u32, other: u32) -> Formula {
+        Formula::NotCompleted(FormulaField { id, next_id: other, prev_id: other })
+    }
+
+    #[test]
+    fn impl_i_branches() {
+        assert_eq!(ImplI {}.create_branches(&seq(vec![c()], imp(a(), b()))), (Some(vec![seq(vec![a(), c()], b())]), 0));
+        assert_eq!(ImplI {}.create_branches(&seq(vec![c()], and(a(), b()))), (None, 0));
+    }
+
+    #[test]
+    fn impl_e_branches() {
+        assert_eq!(ImplE {}.create_branches(&seq(vec![c()], b())), (Some(vec![
+            seq(vec![c()], imp(field(0), b())),
+            seq(vec![c()], field(0)),
+        ]), 1));
+    }
+
+    #[test]
+    fn and_i_branches() {
+        assert_eq!(AndI {}.create_branches(&seq(vec![c()], and(a(), b()))), (Some(vec![seq(vec![c()], a()), seq(vec![c()], b())]), 0));
+        assert_eq!(AndI {}.create_branches(&seq(vec![c()], or(a(), b()))), (None, 0));
+    }
+
+    #[test]
+    fn and_e_branches() {
+        assert_eq!(AndE {}.create_branches(&seq(vec![c()], a())), (Some(vec![
+            seq(vec![c()], and(linked_field(0, 1), linked_field(1, 0))),
+        ]), 2));
+    }
+
+    #[test]
+    fn or_i_branches() {
+        assert_eq!(OrI {}.create_branches(&seq(vec![c()], or(a(), b()))), (Some(vec![seq(vec![c()], field(0))]), 1));
+        assert_eq!(OrI {}.create_branches(&seq(vec![c()], a())), (None, 0));
+    }
+
+    #[test]
+    fn or_e_branches() {
+        assert_eq!(OrE {}.create_branches(&seq(vec![c()], b())), (Some(vec![
+            seq(vec![c(), linked_field(0, 1)], b()),
+            seq(vec![c(), linked_field(1, 0)], b()),
+            seq(vec![c()], or(linked_field(0, 1), linked_field(1, 0))),
+        ]), 2));
+    }
+
+    #[test]
+    fn not_i_branches() {
+        assert_eq!(NotI {}.create_branches(&seq(vec![c()], not(a()))), (Some(vec![seq(vec![c(), a()], bottom())]), 0));
+        assert_eq!(NotI {}.create_branches(&seq(vec![c()], a())), (None, 0));
+    }
+
+    #[test]
+    fn not_e_branches() {
+        assert_eq!(NotE {}.create_branches(&seq(vec![c()], bottom())), (Some(vec![
+            seq(vec![c()], field(0)),
+            seq(vec![c()], not(field(0))),
+        ]), 1));
+        assert_eq!(NotE {}.create_branches(&seq(vec![c()], a())), (None, 0));
+    }
+
+    #[test]
+    fn top_i_branches() {
+        assert_eq!(TopI {}.create_branches(&seq(vec![], top())), (Some(vec![]), 0));
+        assert_eq!(TopI {}.create_branches(&seq(vec![], a())), (None, 0));
+    }
+
+    #[test]
+    fn bottom_e_branches() {
+        assert_eq!(BottomE {}.create_branches(&seq(vec![c()], a())), (Some(vec![seq(vec![c()], bottom())]), 0));
+    }
+
+    #[test]
+    fn raa_branches() {
+        assert_eq!(RAA {}.create_branches(&seq(vec![c()], a())), (Some(vec![seq(vec![c(), not(a())], bottom())]), 0));
+    }
+
+    #[test]
+    fn axiom_branches() {
+        assert_eq!(Axiom {}.create_branches(&seq(vec![b(), a()], a())), (Some(vec![]), 0));
+        assert_eq!(Axiom {}.create_branches(&seq(vec![b()], a())), (None, 0));
+    }
+}
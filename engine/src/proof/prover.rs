@@ -0,0 +1,615 @@
+// Automatic proof search in natural deduction, for the rules of natural_logic.
+// Every node is built with the create_branches of its rule, so the proofs can be displayed and checked like the ones of the player.
+//
+// The search is guided by the classical decision procedure: only goals that are provable are explored.
+// Hypotheses are decomposed until they contain an obvious contradiction (Hintikka sets), RAA is used as a last resort.
+use super::*;
+use super::natural_logic::*;
+
+/// Maximum number of nested sequents in a proof, to stop on formulas that are too big.
+const MAX_SEARCH_DEPTH: usize = 200;
+
+/// Maximum number of sequents explored by find_shortest_proof, which can take seconds on big sequents
+const MAX_SHORTEST_STEPS: u32 = 5_000;
+
+/// Builds a complete proof of the sequent, with every field filled.
+/// Returns None if the sequent is not provable or still has fields to complete.
+/// The ids of the nodes start at next_id, which is increased like in sequent_as_empty_proof.
+pub fn find_proof(s: &Sequent, time: f32, next_id: &mut u32) -> Option<Proof> {
+    return find_proof_with_rules(s, &RuleFilter::default(), time, next_id);
+}
+
+/// Same as find_proof, using only the rules allowed by the filter.
+/// The search is made for every rule, so it may miss a proof when some are banned.
+pub fn find_proof_with_rules(s: &Sequent, rules: &RuleFilter, time: f32, next_id: &mut u32) -> Option<Proof> {
+    if s.after.len() != 1 || calcul::is_provable(s) != Some(true) {
+        return None;
+    }
+
+    let mut search = Search::new(rules, time, *next_id, u32::MAX);
+    let res = search.prove(s.clone(), u32::MAX);
+    *next_id = search.next_id;
+    return res;
+}
+
+/// Proof with the fewest rules among the ones the prover can find, by searching again with more and more rules allowed.
+/// A shorter proof can exist, with ideas the prover doesn't have.
+/// Slower than find_proof: on big sequents, the search stops after MAX_SHORTEST_STEPS and returns the proof of find_proof.
+pub fn find_shortest_proof(s: &Sequent, time: f32, next_id: &mut u32) -> Option<Proof> {
+    let rules = RuleFilter::default();
+    let mut shortest = find_proof_with_rules(s, &rules, time, next_id)?;
+    let mut search = Search::new(&rules, time, *next_id, MAX_SHORTEST_STEPS);
+
+    // Iterative deepening: the first proof found with at most max_rules rules is the shortest
+    for max_rules in 1..count_rules(&shortest) {
+        if let Some(proof) = search.prove(s.clone(), max_rules) {
+            shortest = proof;
+            break;
+        }
+        if search.steps == 0 {
+            break;
+        }
+    }
+
+    *next_id = search.next_id;
+    return Some(shortest);
+}
+
+/// First step of a proof of a sequent
+#[derive(Clone, Debug)]
+pub struct Hint {
+    pub rule_id: u32,
+    /// Formulas to type in the fields created by the rule, in the order of their ids
+    pub fields: Vec<Formula>,
+}
+
+/// Suggests a rule allowed by the filter that keeps the sequent provable. Returns None if no proof is found with these rules.
+pub fn get_hint(s: &Sequent, rules: &RuleFilter) -> Option<Hint> {
+    let proof = find_proof_with_rules(s, rules, 0.0, &mut 0)?;
+    let rule_id = proof.rule_id?;
+
+    let mut fields: Vec<(u32, Formula)> = match_rule_branches(get_system().rules[rule_id as usize].as_ref(), &proof)?
+        .into_iter()
+        .collect();
+    fields.sort_by_key(|(id, _)| *id);
+
+    return Some(Hint {
+        rule_id,
+        fields: fields.into_iter().map(|(_, f)| f).collect(),
+    });
+}
+
+/// How to prove a sequent
+enum Plan {
+    /// Let the search decide
+    Search,
+    /// Apply the rule with these formulas in its fields, and prove each premise with the corresponding plan
+    Rule(u32, Vec<Formula>, Vec<Plan>),
+}
+
+struct Search {
+    system: LogicSystem,
+    /// Plans with another rule are skipped
+    rules: RuleFilter,
+    time: f32,
+    next_id: u32,
+    /// Sequents being proven, to avoid going in circles
+    path: Vec<Sequent>,
+    /// Number of sequents that can still be explored
+    steps: u32,
+}
+
+impl Search {
+    fn new(rules: &RuleFilter, time: f32, next_id: u32, steps: u32) -> Search {
+        return Search { system: get_system(), rules: rules.clone(), time, next_id, path: vec![], steps };
+    }
+
+    /// Proof with at most max_rules rules
+    fn prove(&mut self, s: Sequent, max_rules: u32) -> Option<Proof> {
+        if max_rules == 0 || self.steps == 0 || self.path.len() >= MAX_SEARCH_DEPTH || self.path.iter().any(|p| same_sequent(p, &s)) {
+            return None;
+        }
+
+        self.steps -= 1;
+
+        self.path.push(s.clone());
+
+        let phases: [fn(&Sequent) -> Vec<Plan>; 6] = if is_op(&s.after[0], OperatorType::Bottom) {
+            [closing_plans, decomposition_plans, implication_plans, negation_plans, no_plans, no_plans]
+        } else {
+            [closing_plans, introduction_plans, decomposition_plans, implication_plans, absurd_plans, raa_plans]
+        };
+
+        let mut res = None;
+        'phases: for phase in phases {
+            for plan in phase(&s) {
+                res = self.follow(s.clone(), plan, max_rules);
+                if res.is_some() {
+                    break 'phases;
+                }
+            }
+        }
+
+        self.path.pop();
+        return res;
+    }
+
+    fn follow(&mut self, s: Sequent, plan: Plan, max_rules: u32) -> Option<Proof> {
+        let Plan::Rule(rule_id, fields, plans) = plan else {
+            return self.prove(s, max_rules);
+        };
+        if max_rules == 0 {
+            return None;
+        }
+
+        let rule = &self.system.rules[rule_id as usize];
+        if !self.rules.allows(rule.display_text()) {
+            return None;
+        }
+
+        let mut premises = rule.create_branches(&s).0?;
+        for premise in premises.iter_mut() {
+            for f in premise.before.iter_mut().chain(premise.after.iter_mut()) {
+                fill_fields(f, &fields);
+            }
+        }
+
+        if premises.len() != plans.len() || !premises.iter().all(is_sequent_completed) {
+            return None;
+        }
+
+        // Each premise needs at least one rule, the rest goes to the first ones
+        let mut remaining = max_rules - 1;
+        let premise_count = premises.len() as u32;
+        let mut branches = Vec::with_capacity(premises.len());
+
+        for (i, (premise, plan)) in premises.into_iter().zip(plans).enumerate() {
+            let reserved = premise_count - 1 - i as u32;
+            let branch = self.follow(premise, plan, remaining.checked_sub(reserved)?)?;
+            remaining -= count_rules(&branch);
+            branches.push(branch);
+        }
+
+        let mut proof = sequent_as_empty_proof(s, self.time, &mut self.next_id);
+        proof.rule_id = Some(rule_id);
+        proof.rule_set_time = self.time;
+        proof.branches = branches;
+
+        if !self.system.rules[rule_id as usize].check_validity(&proof) {
+            return None;
+        }
+        return Some(proof);
+    }
+}
+
+/// Replaces the fields with the formula of the same id
+fn fill_fields(f: &mut Formula, fields: &[Formula]) {
+    match f {
+        Formula::Operator(o) => {
+            for arg in o.arg1.iter_mut().chain(o.arg2.iter_mut()) {
+                fill_fields(arg, fields);
+            }
+        },
+        Formula::Variable(_) => (),
+        Formula::NotCompleted(field) => {
+            if let Some(value) = fields.get(field.id as usize) {
+                *f = value.clone();
+            }
+        },
+    }
+}
+
+/// Same goal and same hypotheses, ignoring order and repetitions
+fn same_sequent(a: &Sequent, b: &Sequent) -> bool {
+    a.after == b.after
+        && a.before.iter().all(|f| b.before.contains(f))
+        && b.before.iter().all(|f| a.before.contains(f))
+}
+
+fn is_op(f: &Formula, op: OperatorType) -> bool {
+    matches!(f, Formula::Operator(o) if o.operator_type == op)
+}
+
+/// Arguments of an operator, None for the missing ones
+fn args(f: &Formula) -> (Option<&Formula>, Option<&Formula>) {
+    match f {
+        Formula::Operator(o) => (o.arg1.as_deref(), o.arg2.as_deref()),
+        _ => (None, None),
+    }
+}
+
+fn constant(operator_type: OperatorType) -> Formula {
+    Formula::Operator(Operator { operator_type, arg1: None, arg2: None })
+}
+
+fn negation(f: &Formula) -> Formula {
+    Formula::Operator(Operator { operator_type: OperatorType::Not, arg1: Some(Box::new(f.clone())), arg2: None })
+}
+
+fn provable(before: &[Formula], after: &Formula) -> bool {
+    calcul::is_provable(&Sequent { before: before.to_vec(), after: vec![after.clone()], variables: Vec::new() }) == Some(true)
+}
+
+fn axiom() -> Plan {
+    Plan::Rule(AXIOM, vec![], vec![])
+}
+
+/// Proves the goal with f as an extra hypothesis, where f is proven with prove_f.
+/// Uses (f → goal) and f, then f → goal is introduced. If f is the goal, prove_f is enough.
+fn cut(s: &Sequent, f: &Formula, prove_f: Plan) -> Plan {
+    if s.after[0] == *f {
+        return prove_f;
+    }
+
+    Plan::Rule(IMPL_E, vec![f.clone()], vec![
+        Plan::Rule(IMPL_I, vec![], vec![Plan::Search]),
+        prove_f,
+    ])
+}
+
+/// Derives ⊥ from f and ¬f, where f is proven with prove_f
+fn contradiction(f: &Formula, prove_f: Plan) -> Plan {
+    Plan::Rule(NOT_E, vec![f.clone()], vec![prove_f, axiom()])
+}
+
+fn no_plans(_: &Sequent) -> Vec<Plan> {
+    vec![]
+}
+
+/// The goal is a hypothesis, ⊤, or the hypotheses contradict each other
+fn closing_plans(s: &Sequent) -> Vec<Plan> {
+    let goal = &s.after[0];
+    let ctx = &s.before;
+
+    // Plan to prove ⊥
+    let contradiction_plan = if ctx.contains(&constant(OperatorType::Bottom)) {
+        Some(axiom())
+    }
+    else if let Some(f) = ctx.iter().find(|f| ctx.contains(&negation(f))) {
+        Some(contradiction(f, axiom()))
+    }
+    else if ctx.contains(&negation(&constant(OperatorType::Top))) {
+        Some(contradiction(&constant(OperatorType::Top), Plan::Rule(TOP_I, vec![], vec![])))
+    }
+    else {
+        None
+    };
+
+    if ctx.contains(goal) {
+        return vec![axiom()];
+    }
+    if is_op(goal, OperatorType::Top) {
+        return vec![Plan::Rule(TOP_I, vec![], vec![])];
+    }
+
+    match contradiction_plan {
+        Some(plan) if is_op(goal, OperatorType::Bottom) => vec![plan],
+        Some(plan) => vec![Plan::Rule(BOTTOM_E, vec![], vec![plan])],
+        None => vec![],
+    }
+}
+
+/// Introduction rule of the main operator of the goal
+fn introduction_plans(s: &Sequent) -> Vec<Plan> {
+    let goal = &s.after[0];
+
+    let Formula::Operator(o) = goal else {
+        return vec![];
+    };
+
+    match o.operator_type {
+        OperatorType::Impl => vec![Plan::Rule(IMPL_I, vec![], vec![Plan::Search])],
+        OperatorType::Not => vec![Plan::Rule(NOT_I, vec![], vec![Plan::Search])],
+        OperatorType::And => vec![Plan::Rule(AND_I, vec![], vec![Plan::Search, Plan::Search])],
+        OperatorType::Or => {
+            let (a, b) = args(goal);
+            [a, b].into_iter().flatten()
+                .filter(|side| provable(&s.before, side))
+                .map(|side| Plan::Rule(OR_I, vec![side.clone()], vec![Plan::Search]))
+                .collect()
+        },
+        _ => vec![],
+    }
+}
+
+/// Hypotheses that can be broken down whatever the goal is: A ∧ B, A ∨ B and ¬(A ∨ B).
+/// The parts that are the goal come first, they are proven by the elimination rule without a cut.
+fn decomposition_plans(s: &Sequent) -> Vec<Plan> {
+    let goal = &s.after[0];
+    let ctx = &s.before;
+    let mut direct = vec![];
+    let mut res = vec![];
+
+    for h in ctx.iter() {
+        let (Some(a), Some(b)) = args(h) else { continue };
+
+        if is_op(h, OperatorType::And) {
+            for side in [a, b] {
+                if !ctx.contains(side) {
+                    let plan = cut(s, side, Plan::Rule(AND_E, vec![a.clone(), b.clone()], vec![axiom()]));
+                    if side == goal { direct.push(plan) } else { res.push(plan) }
+                }
+            }
+        }
+        if is_op(h, OperatorType::Or) && !ctx.contains(a) && !ctx.contains(b) {
+            res.push(Plan::Rule(OR_E, vec![a.clone(), b.clone()], vec![Plan::Search, Plan::Search, axiom()]));
+        }
+    }
+
+    for h in ctx.iter() {
+        let (Some(inner), None) = args(h) else { continue };
+        let (Some(a), Some(b)) = args(inner) else { continue };
+
+        if is_op(inner, OperatorType::Or) {
+            for side in [a, b] {
+                if !ctx.contains(&negation(side)) {
+                    // From side, A ∨ B follows, which contradicts ¬(A ∨ B)
+                    let refute_side = Plan::Rule(NOT_I, vec![], vec![
+                        contradiction(inner, Plan::Rule(OR_I, vec![side.clone()], vec![axiom()])),
+                    ]);
+                    let plan = cut(s, &negation(side), refute_side);
+                    if negation(side) == *goal { direct.push(plan) } else { res.push(plan) }
+                }
+            }
+        }
+    }
+
+    direct.append(&mut res);
+    return direct;
+}
+
+/// Use a hypothesis A → B to get B, when A can be proven.
+/// For ⊥, A is always provable: its proof will add ¬A to the hypotheses if needed.
+fn implication_plans(s: &Sequent) -> Vec<Plan> {
+    let goal = &s.after[0];
+    let ctx = &s.before;
+    let mut res = vec![];
+
+    for h in ctx.iter().filter(|h| is_op(h, OperatorType::Impl)) {
+        let (Some(a), Some(b)) = args(h) else { continue };
+
+        if ctx.contains(b) || ctx.contains(&negation(a)) {
+            continue;
+        }
+        if !is_op(goal, OperatorType::Bottom) && !provable(ctx, a) {
+            continue;
+        }
+
+        res.push(cut(s, b, Plan::Rule(IMPL_E, vec![a.clone()], vec![axiom(), Plan::Search])));
+    }
+
+    return res;
+}
+
+/// To prove ⊥ with a hypothesis ¬A, prove A
+fn negation_plans(s: &Sequent) -> Vec<Plan> {
+    let ctx = &s.before;
+    let mut res = vec![];
+
+    for h in ctx.iter().filter(|h| is_op(h, OperatorType::Not)) {
+        let (Some(inner), None) = args(h) else { continue };
+
+        let useful = match args(inner) {
+            // ¬(A ∧ B) is known if ¬A or ¬B is
+            (Some(a), Some(b)) if is_op(inner, OperatorType::And) => !ctx.contains(&negation(a)) && !ctx.contains(&negation(b)),
+            // ¬(A → B) is known if A and ¬B are
+            (Some(a), Some(b)) if is_op(inner, OperatorType::Impl) => !ctx.contains(a) || !ctx.contains(&negation(b)),
+            // ¬¬A is known if A is
+            (Some(a), None) => !ctx.contains(a),
+            _ => false,
+        };
+
+        if useful {
+            res.push(contradiction(inner, Plan::Search));
+        }
+    }
+
+    // The classical way, if proving A never ends
+    for h in ctx.iter().filter(|h| is_op(h, OperatorType::Not)) {
+        let (Some(inner), None) = args(h) else { continue };
+
+        match args(inner) {
+            (Some(a), Some(b)) if is_op(inner, OperatorType::Impl) => {
+                if !ctx.contains(a) {
+                    // If ¬A, then A → B, which contradicts ¬(A → B)
+                    let prove_a = Plan::Rule(RAA_RULE, vec![], vec![
+                        contradiction(inner, Plan::Rule(IMPL_I, vec![], vec![
+                            Plan::Rule(BOTTOM_E, vec![], vec![contradiction(a, axiom())]),
+                        ])),
+                    ]);
+                    res.push(cut(s, a, prove_a));
+                }
+                if !ctx.contains(&negation(b)) {
+                    // If B, then A → B, which contradicts ¬(A → B)
+                    let refute_b = Plan::Rule(NOT_I, vec![], vec![
+                        contradiction(inner, Plan::Rule(IMPL_I, vec![], vec![axiom()])),
+                    ]);
+                    res.push(cut(s, &negation(b), refute_b));
+                }
+            },
+            (Some(a), None) if !ctx.contains(a) => {
+                // If ¬A, it contradicts ¬¬A
+                let prove_a = Plan::Rule(RAA_RULE, vec![], vec![contradiction(&negation(a), axiom())]);
+                res.push(cut(s, a, prove_a));
+            },
+            _ => (),
+        }
+    }
+
+    return res;
+}
+
+/// The hypotheses are contradictory by themselves
+fn absurd_plans(s: &Sequent) -> Vec<Plan> {
+    if provable(&s.before, &constant(OperatorType::Bottom)) {
+        return vec![Plan::Rule(BOTTOM_E, vec![], vec![Plan::Search])];
+    }
+    return vec![];
+}
+
+/// Suppose the goal is false to get a contradiction
+fn raa_plans(s: &Sequent) -> Vec<Plan> {
+    if s.before.contains(&negation(&s.after[0])) {
+        return vec![Plan::Rule(BOTTOM_E, vec![], vec![Plan::Search])];
+    }
+    return vec![Plan::Rule(RAA_RULE, vec![], vec![Plan::Search])];
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::test_utils::*;
+
+    fn check(before: Vec<Formula>, after: Formula) -> Proof {
+        let s = seq(before, after);
+        let mut next_id = 0;
+        let proof = find_proof(&s, 0.0, &mut next_id).unwrap_or_else(|| panic!("No proof found for {:?}", s));
+
+        assert_eq!(proof.root, s);
+        assert!(verify_proof(&proof, &get_system()).is_ok(), "Invalid proof for {:?}", s);
+        return proof;
+    }
+
+    fn uses_raa(p: &Proof) -> bool {
+        p.rule_id == Some(RAA_RULE) || p.branches.iter().any(uses_raa)
+    }
+
+    #[test]
+    fn level_sequents() {
+        check(vec![a()], not(not(a())));
+        check(vec![not(not(a()))], a());
+        check(vec![or(a(), and(b(), c()))], and(or(a(), b()), or(a(), c())));
+        check(vec![and(or(a(), b()), or(a(), c()))], or(a(), and(b(), c())));
+        check(vec![and(a(), or(b(), c()))], or(and(a(), b()), and(a(), c())));
+        check(vec![or(and(a(), b()), and(a(), c()))], and(a(), or(b(), c())));
+        check(vec![imp(and(a(), b()), c())], imp(a(), imp(b(), c())));
+        check(vec![imp(a(), imp(b(), c()))], imp(and(a(), b()), c()));
+        check(vec![], or(a(), not(a())));
+        check(vec![not(or(a(), b()))], and(not(a()), not(b())));
+        check(vec![and(not(a()), not(b()))], not(or(a(), b())));
+        check(vec![not(and(a(), b()))], or(not(a()), not(b())));
+        check(vec![or(not(a()), not(b()))], not(and(a(), b())));
+        check(vec![or(a(), b())], imp(not(a()), b()));
+        check(vec![imp(not(a()), b())], or(a(), b()));
+        check(vec![not(or(a(), not(a())))], or(a(), not(a())));
+        check(vec![imp(not(not(a())), a())], imp(imp(not(a()), a()), a()));
+        check(vec![imp(a(), b())], imp(not(b()), not(a())));
+        check(vec![imp(a(), b())], or(not(a()), b()));
+        check(vec![or(not(a()), b())], imp(a(), b()));
+        check(vec![imp(imp(a(), b()), imp(b(), a()))], imp(b(), a()));
+        check(vec![and(a(), and(b(), c()))], and(and(a(), b()), c()));
+        check(vec![or(a(), or(b(), c()))], or(or(a(), b()), c()));
+        check(vec![not(a())], imp(a(), bottom()));
+        check(vec![imp(a(), bottom())], not(a()));
+        check(vec![not(imp(a(), b()))], and(a(), not(b())));
+        check(vec![], imp(imp(imp(a(), b()), a()), a()));
+        check(vec![], top());
+        check(vec![bottom()], a());
+    }
+
+    #[test]
+    fn intuitionistic_proofs_avoid_raa() {
+        assert!(!uses_raa(&check(vec![a()], not(not(a())))));
+        assert!(!uses_raa(&check(vec![imp(and(a(), b()), c())], imp(a(), imp(b(), c())))));
+        assert!(!uses_raa(&check(vec![imp(a(), b())], imp(not(b()), not(a())))));
+        assert!(!uses_raa(&check(vec![or(not(a()), not(b()))], not(and(a(), b())))));
+        assert!(!uses_raa(&check(vec![not(not(not(a())))], not(a()))));
+    }
+
+    #[test]
+    fn eliminations_without_detours() {
+        for s in [seq(vec![and(a(), b())], a()), seq(vec![and(a(), b())], b()), seq(vec![and(c(), a()), and(a(), b())], b())] {
+            let proof = check(s.before, s.after[0].clone());
+            assert_eq!(proof.rule_id, Some(AND_E));
+            assert_eq!(count_rules(&proof), 2);
+        }
+    }
+
+    #[test]
+    fn unprovable_sequents() {
+        let mut next_id = 0;
+        assert!(find_proof(&seq(vec![a()], b()), 0.0, &mut next_id).is_none());
+        assert!(find_proof(&seq(vec![imp(a(), b())], imp(b(), a())), 0.0, &mut next_id).is_none());
+        assert!(find_proof(&seq(vec![a()], field(0)), 0.0, &mut next_id).is_none());
+    }
+
+    #[test]
+    fn hints() {
+        let all = RuleFilter::default();
+
+        let hint = get_hint(&seq(vec![and(a(), b())], a()), &all).unwrap();
+        assert_eq!(hint.rule_id, AND_E);
+        assert_eq!(hint.fields, vec![a(), b()]);
+
+        let hint = get_hint(&seq(vec![and(a(), b())], b()), &all).unwrap();
+        assert_eq!(hint.rule_id, AND_E);
+        assert_eq!(hint.fields, vec![a(), b()]);
+
+        let hint = get_hint(&seq(vec![imp(a(), b()), a()], b()), &all).unwrap();
+        assert_eq!(hint.rule_id, IMPL_E);
+        assert_eq!(hint.fields, vec![a()]);
+
+        let hint = get_hint(&seq(vec![a()], imp(b(), a())), &all).unwrap();
+        assert_eq!(hint.rule_id, IMPL_I);
+        assert!(hint.fields.is_empty());
+
+        assert!(get_hint(&seq(vec![a()], b()), &all).is_none());
+
+        // Without ⊥e, the contradiction is used through RAA
+        let no_bottom = RuleFilter { allowed: None, banned: vec![String::from("⊥e")] };
+        assert_eq!(get_hint(&seq(vec![not(a()), a()], b()), &all).unwrap().rule_id, BOTTOM_E);
+        assert_eq!(get_hint(&seq(vec![not(a()), a()], b()), &no_bottom).unwrap().rule_id, RAA_RULE);
+
+        let only_intro = RuleFilter { allowed: Some(vec![String::from("∧i"), String::from("Ax")]), banned: vec![] };
+        assert!(get_hint(&seq(vec![and(a(), b())], b()), &only_intro).is_none());
+    }
+
+    #[test]
+    fn restricted_rules() {
+        let rules = RuleFilter { allowed: Some(vec![String::from("→i"), String::from("→e"), String::from("¬i"), String::from("¬e"), String::from("Ax")]), banned: vec![] };
+        let s = seq(vec![imp(a(), b())], imp(not(b()), not(a())));
+        let proof = find_proof_with_rules(&s, &rules, 0.0, &mut 0).unwrap();
+        assert!(verify_proof(&proof, &get_system()).is_ok());
+        assert!(!uses_rule(&proof, AND_E) && !uses_rule(&proof, BOTTOM_E) && !uses_rule(&proof, RAA_RULE));
+
+        // Only provable with RAA
+        let no_raa = RuleFilter { allowed: None, banned: vec![String::from("RAA")] };
+        assert!(find_proof_with_rules(&seq(vec![not(not(a()))], a()), &no_raa, 0.0, &mut 0).is_none());
+        assert!(find_proof_with_rules(&seq(vec![a()], not(not(a()))), &no_raa, 0.0, &mut 0).is_some());
+    }
+
+    #[test]
+    fn shortest_proofs() {
+        let s = seq(vec![not(or(a(), b()))], and(not(a()), not(b())));
+        let first = find_proof(&s, 0.0, &mut 0).unwrap();
+        let shortest = find_shortest_proof(&s, 0.0, &mut 0).unwrap();
+        assert_eq!(shortest.root, s);
+        assert!(verify_proof(&shortest, &get_system()).is_ok());
+        assert!(count_rules(&shortest) < count_rules(&first));
+        assert_eq!(count_rules(&shortest), 11);
+
+        assert_eq!(count_rules(&find_shortest_proof(&seq(vec![and(a(), b())], a()), 0.0, &mut 0).unwrap()), 2);
+        assert!(find_shortest_proof(&seq(vec![a()], b()), 0.0, &mut 0).is_none());
+    }
+
+    #[test]
+    fn random_sequents() {
+        let mut rng = TestRng(0xded0c);
+        let mut proven = 0;
+
+        for _ in 0..1000 {
+            let s = random_sequent(&mut rng, 3, 3);
+            if calcul::is_provable(&s) != Some(true) {
+                continue;
+            }
+
+            let mut next_id = 0;
+            let proof = find_proof(&s, 0.0, &mut next_id).unwrap_or_else(|| panic!("No proof found for {:?}", s));
+            assert!(verify_proof(&proof, &get_system()).is_ok(), "Invalid proof for {:?}", s);
+            proven += 1;
+        }
+
+        assert!(proven > 100);
+    }
+}
@@ -0,0 +1,366 @@
+// Export of proofs to SVG, with the same layout as in game. Doesn't need a GPU.
+use std::collections::HashMap;
+
+use super::*;
+use super::layout::*;
+
+/// Pixels per screen unit. The layout is done for a 1080 pixels high screen.
+pub const PIXELS_PER_UNIT: f32 = 540.0;
+/// Space around the proof (screen units)
+pub const EXPORT_MARGIN: f32 = 50e-3;
+
+pub const TEXT_FONT: &str = "CMU Serif, serif";
+pub const SYMBOL_FONT: &str = "JuliaMono, monospace";
+
+/// RGBA, between 0 and 1
+pub type SvgColor = [f32; 4];
+
+/// Colors of the exported proof, usually taken from the game theme
+#[derive(Clone, Copy, Debug)]
+pub struct SvgTheme {
+    pub background: SvgColor,
+    pub text: SvgColor,
+    pub bar: SvgColor,
+    pub invalid: SvgColor,
+    pub unprovable: SvgColor,
+    pub field: SvgColor,
+}
+
+struct SvgInfo<'a> {
+    char_widths: &'a HashMap<char, f32>,
+    logic_system: &'a LogicSystem,
+    theme: &'a SvgTheme,
+    elements: Vec<String>,
+    /// Bounds of the drawn elements (screen units)
+    right: f32,
+    top: f32,
+}
+
+impl LayoutInfo for SvgInfo<'_> {
+    fn char_widths(&self) -> &HashMap<char, f32> {
+        self.char_widths
+    }
+
+    fn scale(&self) -> f32 {
+        1.0
+    }
+
+    // No animations: everything is drawn in its final state
+    fn appear_scale(&mut self, _creation_time: f32) -> f32 {
+        1.0
+    }
+
+    fn field_scale(&mut self, _field_id: u32) -> f32 {
+        1.0
+    }
+}
+
+enum TextAlign {
+    Bottom, Middle
+}
+
+
+/// Whole SVG document of the proof. char_widths are the ones given by crate::rendering::compute_char_sizes.
+pub fn proof_to_svg(p: &Proof, logic_system: &LogicSystem, char_widths: &HashMap<char, f32>, theme: &SvgTheme) -> String {
+    let mut info = SvgInfo {
+        char_widths,
+        logic_system,
+        theme,
+        elements: Vec::new(),
+        right: 0.0,
+        top: 0.0,
+    };
+
+    add_proof(p, (0.0, 0.0), &mut info);
+
+    let left = -EXPORT_MARGIN;
+    let top = info.top + EXPORT_MARGIN;
+    let width = (info.right + EXPORT_MARGIN - left) * PIXELS_PER_UNIT;
+    let height = (top + EXPORT_MARGIN) * PIXELS_PER_UNIT;
+
+    let mut res = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.2}\" height=\"{:.2}\" viewBox=\"{:.2} {:.2} {:.2} {:.2}\">\n",
+        width, height, left * PIXELS_PER_UNIT, svg_y(top), width, height
+    );
+    res += &format!(
+        "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" {}/>\n",
+        left * PIXELS_PER_UNIT, svg_y(top), width, height, fill(theme.background)
+    );
+
+    for element in info.elements.iter() {
+        res += element;
+        res += "\n";
+    }
+
+    res += "</svg>\n";
+
+    return res;
+}
+
+
+fn add_proof(p: &Proof, bottom_left: (f32, f32), info: &mut SvgInfo) {
+    let branches_width = get_proof_branches_width(p, info);
+    let root_width = get_sequent_width(&p.root, info);
+    let total_width = f32::max(branches_width, root_width);
+
+    let root_left_space = (total_width - root_width) * 0.5;
+
+    add_sequent(&p.root, (bottom_left.0 + root_left_space, bottom_left.1), info);
+
+    // Bar
+    let bar_left_pos = if p.branches.len() == 0 { 0.0 } else {
+        f32::min(
+            root_left_space,
+            (get_proof_width(&p.branches[0], info) - get_proof_root_width(&p.branches[0], info)) * 0.5
+        )
+    };
+    let bar_right_pos = if p.branches.len() == 0 { 0.0 } else {
+        f32::min(
+            root_left_space,
+            (get_proof_width(&p.branches[p.branches.len() - 1], info) - get_proof_root_width(&p.branches[p.branches.len() - 1], info)) * 0.5
+        )
+    };
+
+    let bar_x = bottom_left.0 + bar_left_pos;
+    let bar_y = bottom_left.1 + PAR_POSITION;
+    let bar_width = total_width - bar_right_pos - bar_left_pos;
+
+    let (bar_color, rule_color) = if p.is_rule_invalid {
+        (info.theme.invalid, info.theme.invalid)
+    } else if p.is_unprovable {
+        (info.theme.unprovable, info.theme.unprovable)
+    } else {
+        (info.theme.bar, info.theme.text)
+    };
+
+    add_rect((bar_x, bar_y), bar_width, BAR_HEIGHT, bar_color, info);
+
+    // Rule name
+    let mut right = bottom_left.0 + total_width;
+    if let Some(id) = p.rule_id {
+        let text = format!("({})", info.logic_system.rules[id as usize].display_text());
+        let x = bar_x + bar_width + RULE_MARGIN;
+
+        right = f32::max(right, x + add_text(&text, (x, bar_y + BAR_HEIGHT), SYMBOL_FONT, RULE_TEXT_SCALE, TextAlign::Middle, rule_color, info));
+    }
+
+    info.right = f32::max(info.right, right);
+    info.top = f32::max(info.top, bottom_left.1 + LINE_HEIGHT);
+
+    // Branches
+    let mut pos = (bottom_left.0 + (total_width - branches_width) * 0.5, bottom_left.1 + LINE_HEIGHT);
+
+    for child in p.branches.iter() {
+        add_proof(child, pos, info);
+
+        pos.0 += get_proof_width(child, info);
+        pos.0 += PROOF_MARGIN;
+    }
+}
+
+
+fn add_sequent(s: &Sequent, bottom_left: (f32, f32), info: &mut SvgInfo) {
+    let mut pos = bottom_left;
+    let color = info.theme.text;
+
+    for (i, f) in s.before.iter().enumerate() {
+        if i != 0 {
+            pos.0 += add_text(",", pos, TEXT_FONT, 1.0, TextAlign::Bottom, color, info);
+            pos.0 += COMMA_MARGIN;
+        }
+
+        add_formula(f, &s.variables, pos, info);
+        pos.0 += get_formula_width(f, &s.variables, info);
+    }
+
+    if s.before.len() > 0 { pos.0 += SEQUENT_MARGIN };
+
+    pos.0 += add_text("⊢", pos, SYMBOL_FONT, 1.0, TextAlign::Bottom, color, info);
+
+    if s.after.len() > 0 { pos.0 += SEQUENT_MARGIN };
+
+    for (i, f) in s.after.iter().enumerate() {
+        if i != 0 {
+            pos.0 += add_text(",", pos, TEXT_FONT, 1.0, TextAlign::Bottom, color, info);
+            pos.0 += COMMA_MARGIN;
+        }
+
+        add_formula(f, &s.variables, pos, info);
+        pos.0 += get_formula_width(f, &s.variables, info);
+    }
+}
+
+
+fn add_formula(f: &Formula, variables: &[String], bottom_left: (f32, f32), info: &mut SvgInfo) {
+    let color = info.theme.text;
+
+    match f {
+        Formula::Operator(operator) => {
+            let arity = get_operator_arity(operator.operator_type);
+            let mut pos = bottom_left;
+
+            let left_f = if arity == 2 { operator.arg1.as_ref() } else { None };
+            let right_f = if arity == 1 { operator.arg1.as_ref() } else { operator.arg2.as_ref() };
+
+            let priority = get_operator_priority(operator.operator_type);
+
+            if let Some(f) = left_f {
+                let need_p = needs_parentheses(priority, f);
+
+                if need_p {
+                    pos.0 += add_text("(", pos, TEXT_FONT, 1.0, TextAlign::Bottom, color, info);
+                }
+
+                add_formula(f, variables, pos, info);
+                pos.0 += get_formula_width(f, variables, info);
+
+                if need_p {
+                    pos.0 += add_text(")", pos, TEXT_FONT, 1.0, TextAlign::Bottom, color, info);
+                }
+
+                pos.0 += OPERATOR_MARGIN;
+            }
+
+            pos.0 += add_text(get_operator_symbol(operator.operator_type), pos, SYMBOL_FONT, 1.0, TextAlign::Bottom, color, info);
+
+            if let Some(f) = right_f {
+                let need_p = needs_parentheses(priority, f);
+
+                pos.0 += OPERATOR_MARGIN;
+
+                if need_p {
+                    pos.0 += add_text("(", pos, TEXT_FONT, 1.0, TextAlign::Bottom, color, info);
+                }
+
+                add_formula(f, variables, pos, info);
+                pos.0 += get_formula_width(f, variables, info);
+
+                if need_p {
+                    add_text(")", pos, TEXT_FONT, 1.0, TextAlign::Bottom, color, info);
+                }
+            }
+        },
+        Formula::Variable(id) => {
+            let pos = (bottom_left.0, bottom_left.1 + VARIABLE_Y_SHIFT);
+            add_text(&variable_name(variables, *id), pos, TEXT_FONT, 1.0, TextAlign::Bottom, color, info);
+        },
+        Formula::NotCompleted(_) => {
+            let pos = (bottom_left.0, bottom_left.1 + FIELD_Y_SHIFT);
+            add_rect(pos, FIELD_WIDTH, FIELD_HEIGHT + FIELD_Y_SHIFT, info.theme.field, info);
+        },
+    }
+}
+
+
+/// Adds a text element and returns its width (screen units)
+fn add_text(text: &str, position: (f32, f32), font: &str, scale: f32, align: TextAlign, color: SvgColor, info: &mut SvgInfo) -> f32 {
+    let baseline = match align {
+        TextAlign::Bottom => "text-after-edge",
+        TextAlign::Middle => "middle",
+    };
+
+    info.elements.push(format!(
+        "<text x=\"{:.2}\" y=\"{:.2}\" font-family=\"{}\" font-size=\"{:.2}\" dominant-baseline=\"{}\" {}>{}</text>",
+        position.0 * PIXELS_PER_UNIT, svg_y(position.1), font, TEXT_SCALE * scale, baseline, fill(color), escape(text)
+    ));
+
+    return text.chars().map(|c| get_character_width(c, info)).sum::<f32>() * scale;
+}
+
+fn add_rect(bottom_left: (f32, f32), width: f32, height: f32, color: SvgColor, info: &mut SvgInfo) {
+    info.elements.push(format!(
+        "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" {}/>",
+        bottom_left.0 * PIXELS_PER_UNIT, svg_y(bottom_left.1 + height), width * PIXELS_PER_UNIT, height * PIXELS_PER_UNIT, fill(color)
+    ));
+}
+
+/// SVG y axis goes down
+fn svg_y(y: f32) -> f32 {
+    -y * PIXELS_PER_UNIT
+}
+
+fn fill(color: SvgColor) -> String {
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    format!(
+        "fill=\"#{:02x}{:02x}{:02x}\" fill-opacity=\"{}\"",
+        channel(color[0]), channel(color[1]), channel(color[2]), color[3]
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::test_utils::*;
+
+    const CHAR_WIDTH: f32 = 27.0;
+
+    fn char_widths() -> HashMap<char, f32> {
+        ('A'..='Z').chain('a'..='z').chain("¬→∧∨⊤⊥⊢(),".chars()).map(|c| (c, CHAR_WIDTH)).collect()
+    }
+
+    fn theme() -> SvgTheme {
+        SvgTheme {
+            background: [1.0, 1.0, 1.0, 1.0],
+            text: [0.0, 0.0, 0.0, 1.0],
+            bar: [0.2, 0.2, 0.2, 1.0],
+            invalid: [1.0, 0.0, 0.0, 1.0],
+            unprovable: [1.0, 0.6666667, 0.2, 1.0],
+            field: [0.0, 0.0, 1.0, 0.5],
+        }
+    }
+
+    /// A ∧ B ⊢ B ∧ A, with an unprovable branch
+    fn commutation_proof() -> Proof {
+        let mut p = numbered(tree(seq(vec![and(a(), b())], and(b(), a())), "∧i", vec![
+            tree(seq(vec![and(a(), b())], b()), "∧e", vec![
+                node(seq(vec![and(a(), b())], and(field(0), b())), vec![]),
+            ]),
+            node(seq(vec![and(a(), b())], c()), vec![]),
+        ]));
+        p.branches[1].is_unprovable = true;
+
+        p
+    }
+
+    #[test]
+    fn same_width_as_the_game() {
+        let p = commutation_proof();
+        let widths = char_widths();
+        let system = natural_logic::get_system();
+        let res = proof_to_svg(&p, &system, &widths, &theme());
+
+        let mut info = SvgInfo { char_widths: &widths, logic_system: &system, theme: &theme(), elements: Vec::new(), right: 0.0, top: 0.0 };
+        let proof_width = get_proof_width(&p, &mut info);
+
+        // The rule label of the root is on the right of the proof
+        let label_width = 4.0 * CHAR_WIDTH / 1080.0 * 2.0 * RULE_TEXT_SCALE;
+        let width = (proof_width + RULE_MARGIN + label_width + 2.0 * EXPORT_MARGIN) * PIXELS_PER_UNIT;
+
+        assert!(res.starts_with(&format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.2}\"", width)));
+        assert!(res.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn elements() {
+        let res = proof_to_svg(&commutation_proof(), &natural_logic::get_system(), &char_widths(), &theme());
+
+        assert!(res.contains(">(∧i)</text>"));
+        assert!(res.contains(">(∧e)</text>"));
+        assert_eq!(res.matches(">⊢</text>").count(), 4);
+        assert_eq!(res.matches(">∧</text>").count(), 6);
+        assert_eq!(res.matches(">C</text>").count(), 1);
+
+        // Background, 4 bars and a field
+        assert_eq!(res.matches("<rect").count(), 6);
+        assert_eq!(res.matches("fill=\"#0000ff\" fill-opacity=\"0.5\"").count(), 1);
+
+        // Bar of the unprovable branch
+        assert_eq!(res.matches("fill=\"#ffaa33\"").count(), 1);
+    }
+}
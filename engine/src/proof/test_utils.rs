@@ -0,0 +1,106 @@
+// Shortcuts to build formulas and proofs in tests
+
+use super::*;
+
+pub fn var(id: Variable) -> Formula {
+    Formula::Variable(id)
+}
+
+pub fn op(operator_type: OperatorType, arg1: Option<Formula>, arg2: Option<Formula>) -> Formula {
+    Formula::Operator(Operator { operator_type, arg1: arg1.map(Box::new), arg2: arg2.map(Box::new) })
+}
+
+pub fn not(a: Formula) -> Formula { op(OperatorType::Not, Some(a), None) }
+pub fn and(a: Formula, b: Formula) -> Formula { op(OperatorType::And, Some(a), Some(b)) }
+pub fn or(a: Formula, b: Formula) -> Formula { op(OperatorType::Or, Some(a), Some(b)) }
+pub fn imp(a: Formula, b: Formula) -> Formula { op(OperatorType::Impl, Some(a), Some(b)) }
+pub fn top() -> Formula { op(OperatorType::Top, None, None) }
+pub fn bottom() -> Formula { op(OperatorType::Bottom, None, None) }
+
+pub fn field(id: u32) -> Formula {
+    Formula::NotCompleted(FormulaField { id, next_id: id, prev_id: id })
+}
+
+pub fn seq(before: Vec<Formula>, after: Formula) -> Sequent {
+    Sequent { before, after: vec![after], variables: Vec::new() }
+}
+
+/// A proof node with the given premises, as if the player had completed the fields
+pub fn node(root: Sequent, premises: Vec<Sequent>) -> Proof {
+    let mut next_id = 0;
+    let mut p = sequent_as_empty_proof(root, 0.0, &mut next_id);
+    p.branches = premises.into_iter().map(|s| sequent_as_empty_proof(s, 0.0, &mut next_id)).collect();
+    p
+}
+
+pub fn a() -> Formula { var(0) }
+pub fn b() -> Formula { var(1) }
+pub fn c() -> Formula { var(2) }
+
+/// Index of the rule in the natural logic system
+pub fn rule_id(name: &str) -> u32 {
+    natural_logic::get_system().rules.iter().position(|r| r.display_text() == name).unwrap() as u32
+}
+
+/// A proof node with a rule applied, with ids to be set by `numbered`
+pub fn tree(root: Sequent, rule: &str, branches: Vec<Proof>) -> Proof {
+    let mut p = sequent_as_empty_proof(root, 0.0, &mut 0);
+    p.rule_id = Some(rule_id(rule));
+    p.branches = branches;
+    p
+}
+
+/// Gives unique ids to the nodes of the proof
+pub fn numbered(mut p: Proof) -> Proof {
+    fn number(p: &mut Proof, next_id: &mut u32) {
+        p.id = *next_id;
+        *next_id += 1;
+
+        for b in p.branches.iter_mut() {
+            number(b, next_id);
+        }
+    }
+
+    number(&mut p, &mut 0);
+    p
+}
+
+/// Small xorshift generator, so that random tests are reproducible
+pub struct TestRng(pub u64);
+
+impl TestRng {
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn below(&mut self, n: u32) -> u32 {
+        (self.next_u64() % n as u64) as u32
+    }
+}
+
+/// Random formula with at most `depth` nested operators, and variables below `var_count`
+pub fn random_formula(rng: &mut TestRng, depth: u32, var_count: u32) -> Formula {
+    if depth == 0 {
+        return match rng.below(10) {
+            0 => top(),
+            1 => bottom(),
+            _ => var(rng.below(var_count)),
+        };
+    }
+
+    match rng.below(6) {
+        0 => random_formula(rng, 0, var_count),
+        1 => not(random_formula(rng, depth - 1, var_count)),
+        2 => and(random_formula(rng, depth - 1, var_count), random_formula(rng, depth - 1, var_count)),
+        3 => or(random_formula(rng, depth - 1, var_count), random_formula(rng, depth - 1, var_count)),
+        _ => imp(random_formula(rng, depth - 1, var_count), random_formula(rng, depth - 1, var_count)),
+    }
+}
+
+pub fn random_sequent(rng: &mut TestRng, depth: u32, var_count: u32) -> Sequent {
+    let before = (0..rng.below(3)).map(|_| random_formula(rng, depth, var_count)).collect();
+    seq(before, random_formula(rng, depth, var_count))
+}
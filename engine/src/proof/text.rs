@@ -0,0 +1,325 @@
+// Text versions of formulas, sequents and proofs, for terminals, logs, tests, level files and LaTeX.
+// Proofs are laid out like the trees of textbooks: premises above a bar, with the name of the rule on its right.
+
+use std::fmt;
+
+use super::*;
+
+/// Characters used for the symbols. The ASCII spellings can be read back by the parser.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Symbols {
+    Unicode,
+    Ascii,
+    /// Commands of math mode
+    Latex,
+}
+
+/// Space between two premises
+const PREMISES_GAP: usize = 3;
+
+pub fn operator_text(op: OperatorType, symbols: Symbols) -> &'static str {
+    match symbols {
+        Symbols::Unicode => get_operator_symbol(op),
+        Symbols::Ascii => match op {
+            OperatorType::Not => "~",
+            OperatorType::Impl => "->",
+            OperatorType::And => "/\\",
+            OperatorType::Or => "\\/",
+            // T and F can be the names of variables
+            OperatorType::Top => "\\top",
+            OperatorType::Bottom => "\\bot",
+        },
+        Symbols::Latex => match op {
+            OperatorType::Not => "\\lnot",
+            OperatorType::Impl => "\\to",
+            OperatorType::And => "\\land",
+            OperatorType::Or => "\\lor",
+            OperatorType::Top => "\\top",
+            OperatorType::Bottom => "\\bot",
+        },
+    }
+}
+
+fn turnstile_text(symbols: Symbols) -> &'static str {
+    match symbols {
+        Symbols::Unicode => "⊢",
+        Symbols::Latex => "\\vdash",
+        Symbols::Ascii => "|-",
+    }
+}
+
+fn variable_text(variables: &[String], id: u32, symbols: Symbols) -> String {
+    let name = variable_name(variables, id);
+
+    // Multi-letter names would be typeset as a product of variables
+    if symbols == Symbols::Latex && name.chars().count() > 1 {
+        return format!("\\mathit{{{}}}", name);
+    }
+    return name;
+}
+
+/// Formula as text, with the same symbols and parentheses as in game. Fields are written as _.
+pub fn formula_text(f: &Formula, variables: &[String], symbols: Symbols) -> String {
+    match f {
+        Formula::Operator(operator) => {
+            let arity = get_operator_arity(operator.operator_type);
+            let priority = get_operator_priority(operator.operator_type);
+
+            let left_f = if arity == 2 { operator.arg1.as_ref() } else { None };
+            let right_f = if arity == 1 { operator.arg1.as_ref() } else { operator.arg2.as_ref() };
+
+            let arg_text = |f: &Formula| if needs_parentheses(priority, f) {
+                format!("({})", formula_text(f, variables, symbols))
+            } else {
+                formula_text(f, variables, symbols)
+            };
+
+            let mut res = String::new();
+            if let Some(f) = left_f {
+                res += &arg_text(f);
+                res += " ";
+            }
+            res += operator_text(operator.operator_type, symbols);
+            if let Some(f) = right_f {
+                // LaTeX commands end at the first letter
+                if arity == 2 || symbols == Symbols::Latex {
+                    res += " ";
+                }
+                res += &arg_text(f);
+            }
+
+            return res;
+        },
+        Formula::Variable(id) => variable_text(variables, *id, symbols),
+        Formula::NotCompleted(_) if symbols == Symbols::Latex => String::from("\\_"),
+        Formula::NotCompleted(_) => String::from("_"),
+    }
+}
+
+pub fn sequent_text(s: &Sequent, symbols: Symbols) -> String {
+    let before: Vec<String> = s.before.iter().map(|f| formula_text(f, &s.variables, symbols)).collect();
+    let after: Vec<String> = s.after.iter().map(|f| formula_text(f, &s.variables, symbols)).collect();
+
+    let mut res = before.join(", ");
+    if !res.is_empty() {
+        res += " ";
+    }
+    res += turnstile_text(symbols);
+    if !after.is_empty() {
+        res += " ";
+        res += &after.join(", ");
+    }
+
+    return res;
+}
+
+/// Name of a rule, with its symbols written like in the formulas.
+/// In LaTeX, it works both in text and math mode.
+pub fn rule_text(name: &str, symbols: Symbols) -> String {
+    let operators = [OperatorType::Not, OperatorType::Impl, OperatorType::And, OperatorType::Or, OperatorType::Top, OperatorType::Bottom];
+
+    let word_text = |word: &str| match symbols {
+        Symbols::Latex if !word.is_empty() => format!("\\textrm{{{}}}", word),
+        _ => word.to_string(),
+    };
+
+    let mut res = String::new();
+    let mut word = String::new();
+
+    for c in name.chars() {
+        match operators.iter().find(|op| get_operator_symbol(**op).starts_with(c)) {
+            Some(op) => {
+                res += &word_text(&word);
+                word.clear();
+                res += &match symbols {
+                    Symbols::Latex => format!("\\ensuremath{{{}}}", operator_text(*op, symbols)),
+                    _ => operator_text(*op, symbols).to_string(),
+                };
+            },
+            None => word.push(c),
+        }
+    }
+
+    return res + &word_text(&word);
+}
+
+/// Lines of a proof tree, drawn in a rectangle of characters
+struct Block {
+    lines: Vec<String>,
+    width: usize,
+    /// Column and width of the conclusion, on the last line. The bar under the premises is aligned on them.
+    root_start: usize,
+    root_width: usize,
+}
+
+fn text_width(text: &str) -> usize {
+    text.chars().count()
+}
+
+fn proof_block(p: &Proof, system: &LogicSystem, symbols: Symbols) -> Block {
+    let conclusion = sequent_text(&p.root, symbols);
+    let conclusion_width = text_width(&conclusion);
+
+    // Unproven sequents are left as hypotheses
+    let Some(rule_id) = p.rule_id else {
+        return Block { lines: vec![conclusion], width: conclusion_width, root_start: 0, root_width: conclusion_width };
+    };
+
+    let premises: Vec<Block> = p.branches.iter().map(|b| proof_block(b, system, symbols)).collect();
+    let height = premises.iter().map(|b| b.lines.len()).max().unwrap_or(0);
+
+    // Premises side by side, aligned on their conclusions
+    let mut premise_lines = vec![String::new(); height];
+    let mut premises_span: Option<(usize, usize)> = None;
+    let mut x = 0;
+
+    for (i, premise) in premises.iter().enumerate() {
+        if i > 0 {
+            x += PREMISES_GAP;
+        }
+
+        let empty_lines = height - premise.lines.len();
+        for (line_index, line) in premise_lines.iter_mut().enumerate() {
+            let text = if line_index < empty_lines { "" } else { &premise.lines[line_index - empty_lines] };
+            let padding = x - text_width(line);
+            *line += &" ".repeat(padding);
+            *line += text;
+        }
+
+        let start = premises_span.map_or(x + premise.root_start, |(start, _)| start);
+        premises_span = Some((start, x + premise.root_start + premise.root_width));
+        x += premise.width;
+    }
+
+    // The bar covers the conclusions of the premises and the conclusion of the rule, which are centered on each other
+    let (span_start, span_end) = premises_span.unwrap_or((0, 0));
+    let bar_width = usize::max(span_end - span_start, conclusion_width);
+    let span_center = (span_start + span_end) as isize;
+    let bar_start = (span_center - bar_width as isize) / 2;
+
+    // Shift everything right if the bar goes past the left of the premises
+    let shift = (-bar_start).max(0) as usize;
+    let bar_start = (bar_start + shift as isize) as usize;
+    let conclusion_start = bar_start + (bar_width - conclusion_width) / 2;
+
+    let bar = match symbols {
+        Symbols::Unicode => "─",
+        Symbols::Ascii | Symbols::Latex => "-",
+    };
+    let label = format!(" {}", rule_text(system.rules[rule_id as usize].display_text(), symbols));
+
+    let mut lines: Vec<String> = premise_lines.into_iter()
+        .map(|line| if line.is_empty() { line } else { " ".repeat(shift) + &line })
+        .collect();
+    lines.push(" ".repeat(bar_start) + &bar.repeat(bar_width) + &label);
+    lines.push(" ".repeat(conclusion_start) + &conclusion);
+
+    let width = lines.iter().map(|l| text_width(l)).max().unwrap_or(0);
+
+    return Block { lines, width, root_start: conclusion_start, root_width: conclusion_width };
+}
+
+/// Whole proof tree, one line per row of the tree, with the conclusion on the last line
+pub fn proof_tree_text(p: &Proof, system: &LogicSystem, symbols: Symbols) -> String {
+    let block = proof_block(p, system, symbols);
+    let lines: Vec<&str> = block.lines.iter().map(|l| l.trim_end()).collect();
+
+    return lines.join("\n") + "\n";
+}
+
+/// Variables are named with the default letters, since formulas don't know the names of the level.
+/// The alternate flag ({:#}) writes the symbols in ASCII.
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbols = if f.alternate() { Symbols::Ascii } else { Symbols::Unicode };
+        write!(f, "{}", formula_text(self, &[], symbols))
+    }
+}
+
+/// The alternate flag ({:#}) writes the symbols in ASCII
+impl fmt::Display for Sequent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbols = if f.alternate() { Symbols::Ascii } else { Symbols::Unicode };
+        write!(f, "{}", sequent_text(self, symbols))
+    }
+}
+
+/// Proof tree with the rules of natural deduction. The alternate flag ({:#}) writes the symbols in ASCII.
+impl fmt::Display for Proof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbols = if f.alternate() { Symbols::Ascii } else { Symbols::Unicode };
+        write!(f, "{}", proof_tree_text(self, &natural_logic::get_system(), symbols))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::test_utils::*;
+
+    #[test]
+    fn formulas() {
+        assert_eq!(a().to_string(), "A");
+        assert_eq!(imp(and(a(), not(b())), or(c(), bottom())).to_string(), "A ∧ ¬B → C ∨ ⊥");
+        assert_eq!(imp(imp(a(), b()), a()).to_string(), "(A → B) → A");
+        assert_eq!(not(and(a(), top())).to_string(), "¬(A ∧ ⊤)");
+        assert_eq!(format!("{:#}", imp(a(), or(not(b()), field(0)))), "A -> ~B \\/ _");
+    }
+
+    #[test]
+    fn sequents() {
+        assert_eq!(seq(vec![a(), imp(a(), b())], b()).to_string(), "A, A → B ⊢ B");
+        assert_eq!(seq(vec![], or(a(), not(a()))).to_string(), "⊢ A ∨ ¬A");
+        assert_eq!(format!("{:#}", seq(vec![and(a(), b())], a())), "A /\\ B |- A");
+
+        let mut named = seq(vec![imp(a(), b()), a()], b());
+        named.variables = vec![String::from("rain"), String::from("wet")];
+        assert_eq!(named.to_string(), "rain → wet, rain ⊢ wet");
+    }
+
+    #[test]
+    fn ascii_can_be_parsed() {
+        let s = seq(vec![not(a()), imp(b(), top())], or(and(a(), bottom()), c()));
+        let parsed = crate::parser::parse_sequent(&format!("{:#}", s), "test.sq", 1, 1).unwrap();
+        assert_eq!((parsed.before, parsed.after), (s.before, s.after));
+
+        // Variables named like the constants
+        let mut s = seq(vec![not(a()), imp(b(), top())], or(and(a(), bottom()), c()));
+        s.variables = vec![String::from("A"), String::from("T"), String::from("F")];
+        assert_eq!(format!("{:#}", s), "~A, T -> \\top |- (A /\\ \\bot) \\/ F");
+        let parsed = crate::parser::parse_sequent(&format!("{:#}", s), "test.sq", 1, 1).unwrap();
+        assert_eq!((parsed.before, parsed.after), (s.before, s.after));
+
+        // Unnamed variable 5 is F, and reads back as a variable
+        let s = seq(vec![Formula::Variable(5)], Formula::Variable(5));
+        assert_eq!(s.to_string(), "F ⊢ F");
+        let parsed = crate::parser::parse_sequent(&s.to_string(), "test.sq", 1, 1).unwrap();
+        assert_eq!(parsed.variables, vec![String::from("F")]);
+        assert_eq!(parsed.to_string(), "F ⊢ F");
+    }
+
+    #[test]
+    fn trees() {
+        let hypotheses = vec![imp(a(), b()), a()];
+        let p = tree(seq(hypotheses.clone(), b()), "→e", vec![
+            tree(seq(hypotheses.clone(), imp(a(), b())), "Ax", vec![]),
+            tree(seq(hypotheses.clone(), a()), "Ax", vec![]),
+        ]);
+
+        assert_eq!(p.to_string(), concat!(
+            "──────────────── Ax   ──────────── Ax\n",
+            "A → B, A ⊢ A → B      A → B, A ⊢ A\n",
+            "────────────────────────────────── →e\n",
+            "           A → B, A ⊢ B\n",
+        ));
+
+        // Unfinished branches are hypotheses, and a short premise gives a bar as long as the conclusion
+        let p = tree(seq(vec![], imp(a(), a())), "→i", vec![sequent_as_empty_proof(seq(vec![a()], a()), 0.0, &mut 0)]);
+        assert_eq!(format!("{:#}", p), concat!(
+            " A |- A\n",
+            "--------- ->i\n",
+            "|- A -> A\n",
+        ));
+    }
+}
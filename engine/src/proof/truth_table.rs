@@ -0,0 +1,158 @@
+use super::*;
+
+/// One line of a truth table
+#[derive(Clone, Debug)]
+pub struct TruthTableRow {
+    pub valuation: Valuation,
+    pub value: bool,
+}
+
+/// Value of a sequent for every valuation of its variables
+#[derive(Clone, Debug)]
+pub struct TruthTable {
+    /// Variables of the sequent, sorted
+    pub variables: Vec<Variable>,
+    pub rows: Vec<TruthTableRow>,
+}
+
+impl TruthTable {
+    pub fn is_tautology(&self) -> bool {
+        self.rows.iter().all(|r| r.value)
+    }
+
+    /// Rows where the sequent is false
+    pub fn falsifying_rows(&self) -> Vec<&TruthTableRow> {
+        self.rows.iter().filter(|r| !r.value).collect()
+    }
+}
+
+/// Value of the formula. Returns None if the formula has fields to complete.
+/// Panics if a variable of the formula is not in the valuation.
+pub fn eval_formula(f: &Formula, valuation: &Valuation) -> Option<bool> {
+    match f {
+        Formula::Operator(operator) => {
+            let arg = |a: &Option<Box<Formula>>| eval_formula(a.as_ref().unwrap(), valuation);
+
+            let res = match operator.operator_type {
+                OperatorType::Not => !arg(&operator.arg1)?,
+                OperatorType::Impl => !arg(&operator.arg1)? || arg(&operator.arg2)?,
+                OperatorType::And => arg(&operator.arg1)? && arg(&operator.arg2)?,
+                OperatorType::Or => arg(&operator.arg1)? || arg(&operator.arg2)?,
+                OperatorType::Top => true,
+                OperatorType::Bottom => false,
+            };
+
+            return Some(res);
+        },
+        Formula::Variable(v) => Some(valuation[v]),
+        Formula::NotCompleted(_) => None,
+    }
+}
+
+/// A sequent is true if one formula on the right is true or one formula on the left is false.
+pub fn eval_sequent(s: &Sequent, valuation: &Valuation) -> Option<bool> {
+    let mut res = false;
+
+    // Don't stop early, to return None if there is a field anywhere
+    for f in s.before.iter() {
+        res |= !eval_formula(f, valuation)?;
+    }
+    for f in s.after.iter() {
+        res |= eval_formula(f, valuation)?;
+    }
+
+    return Some(res);
+}
+
+/// Sorted list of the variables that appear in the sequent
+pub fn get_variables(s: &Sequent) -> Vec<Variable> {
+    fn add_variables(f: &Formula, res: &mut Vec<Variable>) {
+        match f {
+            Formula::Operator(operator) => {
+                for arg in operator.arg1.iter().chain(operator.arg2.iter()) {
+                    add_variables(arg, res);
+                }
+            },
+            Formula::Variable(v) => res.push(*v),
+            Formula::NotCompleted(_) => (),
+        }
+    }
+
+    let mut res = Vec::new();
+    for f in s.before.iter().chain(s.after.iter()) {
+        add_variables(f, &mut res);
+    }
+
+    res.sort();
+    res.dedup();
+    return res;
+}
+
+/// Evaluates the sequent for all the valuations of its variables. Returns None if the sequent has fields to complete.
+/// Rows are in binary order: the last variable changes first, and false comes before true.
+pub fn truth_table(s: &Sequent) -> Option<TruthTable> {
+    let variables = get_variables(s);
+    let n = variables.len();
+    let mut rows = Vec::with_capacity(1 << n);
+
+    for bits in 0..(1u64 << n) {
+        let valuation: Valuation = variables.iter().enumerate()
+            .map(|(i, v)| (*v, bits & (1 << (n - 1 - i)) != 0))
+            .collect();
+
+        let value = eval_sequent(s, &valuation)?;
+        rows.push(TruthTableRow { valuation, value });
+    }
+
+    return Some(TruthTable { variables, rows });
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::test_utils::*;
+
+    #[test]
+    fn formula_values() {
+        let v = Valuation::from([(0, true), (1, false)]);
+
+        assert_eq!(eval_formula(&imp(a(), b()), &v), Some(false));
+        assert_eq!(eval_formula(&imp(b(), a()), &v), Some(true));
+        assert_eq!(eval_formula(&or(b(), not(a())), &v), Some(false));
+        assert_eq!(eval_formula(&and(a(), top()), &v), Some(true));
+        assert_eq!(eval_formula(&or(bottom(), b()), &v), Some(false));
+        assert_eq!(eval_formula(&and(a(), field(0)), &v), None);
+    }
+
+    #[test]
+    fn sequent_values() {
+        let v = Valuation::from([(0, true), (1, false)]);
+
+        assert_eq!(eval_sequent(&seq(vec![a()], b()), &v), Some(false));
+        assert_eq!(eval_sequent(&seq(vec![a(), b()], b()), &v), Some(true));
+        assert_eq!(eval_sequent(&Sequent { before: vec![a()], after: vec![], variables: vec![] }, &v), Some(false));
+        assert_eq!(eval_sequent(&seq(vec![b()], field(0)), &v), None);
+    }
+
+    #[test]
+    fn table_of_implication() {
+        let table = truth_table(&seq(vec![imp(a(), c())], c())).unwrap();
+
+        assert_eq!(table.variables, vec![0, 2]);
+        assert_eq!(table.rows.iter().map(|r| r.value).collect::<Vec<bool>>(), vec![false, true, true, true]);
+        assert!(!table.is_tautology());
+
+        let falsifying = table.falsifying_rows();
+        assert_eq!(falsifying.len(), 1);
+        assert_eq!(falsifying[0].valuation, Valuation::from([(0, false), (2, false)]));
+    }
+
+    #[test]
+    fn tautologies() {
+        assert!(truth_table(&seq(vec![], or(a(), not(a())))).unwrap().is_tautology());
+        assert!(truth_table(&seq(vec![not(or(a(), b()))], and(not(a()), not(b())))).unwrap().is_tautology());
+        assert!(truth_table(&seq(vec![], top())).unwrap().is_tautology());
+        assert!(truth_table(&seq(vec![a()], field(0))).is_none());
+    }
+}
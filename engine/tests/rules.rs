@@ -0,0 +1,64 @@
+// Rules of natural deduction, used through the public API like another tool would
+
+use sequent_engine::parser::parse_sequent;
+use sequent_engine::proof::natural_logic::*;
+use sequent_engine::proof::*;
+
+fn sequent(text: &str) -> Sequent {
+    parse_sequent(text, "rules.rs", 1, 1).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Replaces each field by the formula of its id
+fn fill(f: &mut Formula, fillers: &[Formula]) {
+    match f {
+        Formula::NotCompleted(field) => *f = fillers[field.id as usize].clone(),
+        Formula::Operator(operator) => {
+            for arg in operator.arg1.iter_mut().chain(operator.arg2.iter_mut()) {
+                fill(arg, fillers);
+            }
+        },
+        Formula::Variable(_) => { },
+    }
+}
+
+#[test]
+fn completed_branches_are_valid() {
+    // Rule, sequent, and the formulas to type in the fields
+    let cases = [
+        (NOT_I, "C - !A", ""),
+        (NOT_E, "C - ⊥", "A"),
+        (IMPL_I, "C - A > B", ""),
+        (IMPL_E, "C - B", "A"),
+        (AND_I, "C - A & B", ""),
+        (AND_E, "C - A", "A, B"),
+        (OR_I, "C - A | B", "B"),
+        (OR_E, "C - B", "A, !A"),
+        (TOP_I, "C - ⊤", ""),
+        (BOTTOM_E, "C - A", ""),
+        (RAA_RULE, "C - A", ""),
+        (AXIOM, "B, A - A", ""),
+    ];
+
+    let system = get_system();
+    for (rule_id, text, fillers) in cases {
+        // Parsed together, so that the variables have the same ids
+        let mut root = sequent(&format!("{}{}{}", fillers, if fillers.is_empty() { "" } else { ", " }, text));
+        let fillers: Vec<Formula> = root.before.drain(..fillers.split(',').filter(|f| !f.trim().is_empty()).count()).collect();
+
+        let rule = system.rules[rule_id as usize].as_ref();
+        let (Some(mut premises), field_count) = rule.create_branches(&root) else { panic!("{} can't be used on {}", rule.display_text(), root) };
+        assert_eq!(field_count as usize, fillers.len(), "{}", rule.display_text());
+
+        for premise in premises.iter_mut() {
+            for f in premise.before.iter_mut().chain(premise.after.iter_mut()) {
+                fill(f, &fillers);
+            }
+        }
+
+        let mut proof = sequent_as_empty_proof(root, 0.0, &mut 0);
+        proof.rule_id = Some(rule_id);
+        proof.branches = premises.into_iter().map(|s| sequent_as_empty_proof(s, 0.0, &mut 0)).collect();
+
+        assert!(rule.check_validity(&proof), "{}:\n{}", rule.display_text(), proof);
+    }
+}
@@ -30,6 +30,11 @@ pub enum Action {
     Restart,
 
     ToggleKeys,
+    ToggleTruthHint,
+    Hint,
+    ExportLatex,
+    ExportSvg,
+    Replay,
 
     Exit,
     Up,
@@ -106,6 +111,11 @@ pub fn get_default_bindings() -> Bindings {
     res.insert(Action::Restart, KeyCode::Back);
 
     res.insert(Action::ToggleKeys, KeyCode::F1);
+    res.insert(Action::ToggleTruthHint, KeyCode::F2);
+    res.insert(Action::Hint, KeyCode::F3);
+    res.insert(Action::ExportLatex, KeyCode::F4);
+    res.insert(Action::ExportSvg, KeyCode::F5);
+    res.insert(Action::Replay, KeyCode::F6);
 
     res.insert(Action::Exit, KeyCode::Escape);
 
@@ -172,6 +182,11 @@ pub fn get_action_name(action: Action) -> String {
         Action::Redo => "Redo".into(),
         Action::Restart => "Restart sequent".into(),
         Action::ToggleKeys => "Toggle ingame UI".into(),
+        Action::ToggleTruthHint => "Toggle truth hint".into(),
+        Action::Hint => "Hint".into(),
+        Action::ExportLatex => "Export proof to LaTeX".into(),
+        Action::ExportSvg => "Export proof to SVG".into(),
+        Action::Replay => "Replay best solution".into(),
         Action::Exit => "Menus Back/Exit".into(),
         Action::Up => "Menus up".into(),
         Action::Down => "Menus down".into(),
@@ -36,6 +36,20 @@ pub const TIMER_MARGIN: f32 = 0.05;
 pub const TIMER_SIZE: f32 = 40.0;
 pub const TIMER_SIZE_BIG: f32 = 60.0;
 
+pub const GENERATING_SIZE: f32 = 40.0;
+
+pub const TRUTH_HINT_Y: f32 = 0.6;
+pub const TRUTH_HINT_SIZE: f32 = 35.0;
+
+pub const DAILY_RESULT_Y: f32 = 0.6;
+pub const DAILY_RESULT_SIZE: f32 = 40.0;
+pub const DAILY_RESULT_LINE_HEIGHT: f32 = 0.1;
+
+pub const HINT_Y: f32 = 0.5;
+pub const HINT_SIZE: f32 = 35.0;
+/// Size of the highlight around the key of the hinted rule, relative to the key
+pub const HINT_HIGHLIGHT_SCALE: f32 = 1.3;
+
 
 pub fn render_ui(special: bool, symbol_font: &Font, draw: &mut Draw, gfx: &Graphics, state: &State) {
     
@@ -61,7 +75,7 @@ pub fn render_ui(special: bool, symbol_font: &Font, draw: &mut Draw, gfx: &Graph
             draw_action_and_text(
                 ScreenPosition { x: -total_size * 0.5 + KEYS_COLUMN_SIZE * i as f32 + KEYS_SCALE_SHIFT_X, y: KEYS_Y },
                 crate::action::Action::InsertVariable(i),
-                &crate::proof::rendering::VARIABLE_LETTERS.chars().nth(i as usize).unwrap().to_string(),
+                &crate::proof::variable_name(&game_state.state.proof.root.variables, i),
                 1.0,
                 *state.settings.theme(), state.settings.bindings(), symbol_font, draw, gfx
             );
@@ -81,12 +95,24 @@ pub fn render_ui(special: bool, symbol_font: &Font, draw: &mut Draw, gfx: &Graph
                 &game_state.logic_system.rules[i]
             };
 
+            let position = ScreenPosition { x: -total_size * 0.5 + KEYS_COLUMN_SIZE * (i/2) as f32 + KEYS_SCALE_SHIFT_X, y: KEYS_Y - KEYS_LINE_HEIGHT * ((i%2) as f32) };
+
+            if game_state.hinted_rule == Some(i as u32) {
+                let size = ScreenSize { x: ACTION_RECT_SIZE * HINT_HIGHLIGHT_SCALE, y: ACTION_RECT_SIZE * HINT_HIGHLIGHT_SCALE };
+                draw.rect(position.subtract(size.scale(0.5)).to_pixel(gfx).as_couple(), size.to_pixel(gfx))
+                    .color(state.settings.theme().ui_button_flash);
+            }
+
+            // Rules the level doesn't allow are greyed out
+            let theme = *state.settings.theme();
+            let theme = if game_state.rules.allows(rule.display_text()) { theme } else { Theme { ui_text: theme.ui_text_transparent, ..theme } };
+
             draw_action_and_text(
-                ScreenPosition { x: -total_size * 0.5 + KEYS_COLUMN_SIZE * (i/2) as f32 + KEYS_SCALE_SHIFT_X, y: KEYS_Y - KEYS_LINE_HEIGHT * ((i%2) as f32) },
+                position,
                 crate::action::Action::InsertRule(i as u32),
                 rule.display_text(),
                 1.0,
-                *state.settings.theme(), state.settings.bindings(), symbol_font, draw, gfx
+                theme, state.settings.bindings(), symbol_font, draw, gfx
             );
         }
     }
@@ -98,6 +124,11 @@ pub fn render_ui(special: bool, symbol_font: &Font, draw: &mut Draw, gfx: &Graph
         crate::action::Action::Restart,
         crate::action::Action::ToggleKeys,
         crate::action::Action::SpecialRuleMode,
+        crate::action::Action::ToggleTruthHint,
+        crate::action::Action::Hint,
+        crate::action::Action::ExportLatex,
+        crate::action::Action::ExportSvg,
+        crate::action::Action::Replay,
     ];
 
     let left_text = [
@@ -107,6 +138,11 @@ pub fn render_ui(special: bool, symbol_font: &Font, draw: &mut Draw, gfx: &Graph
         "Restart",
         "Hide UI",
         "Alt. rules",
+        "Is it true?",
+        "Hint",
+        "LaTeX",
+        "SVG",
+        "Replay",
     ];
 
     for i in 0..left_actions.len() {
@@ -130,55 +166,70 @@ pub fn render_bottom_ui(draw: &mut Draw, gfx: &Graphics, state: &State) {
         _ => unreachable!()
     };
 
-    match game_state.current_level_id {
-        Some(level_id) => {
-            let level = &game_state.get_level(state).unwrap();
-
-            {
-                // Draw level name
-                let text_pos = ScreenPosition { x: 0.0, y: LEVEL_NAME_Y }.to_pixel(gfx);
-                let mut text = draw.text(&state.text_font, &level.name);
-                text.position(text_pos.x, text_pos.y)
-                    .color(state.settings.theme().ui_text)
-                    .v_align_middle()
-                    .h_align_center();
-            
-                set_text_size(&mut text, LEVEL_NAME_SIZE, gfx);
-            }
-
-            {
-                // Draw level index
-                let text_pos = ScreenPosition { x: 0.0, y: LEVEL_CHANGE_Y }.to_pixel(gfx);
-                let index_text = format!("{}/{}", level_id + 1, state.campaigns.get(game_state.current_campaign_id.as_ref().unwrap()).unwrap().levels.len());
-                let mut text = draw.text(&state.text_font, &index_text);
-                text.position(text_pos.x, text_pos.y)
-                    .color(state.settings.theme().ui_text)
-                    .v_align_middle()
-                    .h_align_center();
-            
-                set_text_size(&mut text, LEVEL_CHANGE_SIZE, gfx);
-            }
-
-            // Draw keys
-            draw_action(
-                crate::action::Action::Left,
-                ScreenPosition { x: -LEVEL_CHANGE_KEYS_X, y: LEVEL_CHANGE_Y }, 
-                1.0,
-                *state.settings.theme(), state.settings.bindings(),
-                &state.symbol_font, draw, gfx
-            );
-            draw_action(
-                crate::action::Action::Right,
-                ScreenPosition { x: LEVEL_CHANGE_KEYS_X, y: LEVEL_CHANGE_Y }, 
-                1.0,
-                *state.settings.theme(), state.settings.bindings(),
-                &state.symbol_font, draw, gfx
-            );
+    // Name of the level, and where it is in the campaign. Generated levels are changed with the same keys.
+    let (name, index_text) = match (game_state.current_level_id, &game_state.challenge) {
+        (Some(level_id), _) => {
+            let level = game_state.get_level(state).unwrap();
+            let level_count = state.campaigns.get(game_state.current_campaign_id.as_ref().unwrap()).unwrap().levels.len();
+            (level.name, format!("{}/{}", level_id + 1, level_count))
         },
-        None => {
-            
+        (None, Some(challenge)) => match &game_state.daily {
+            Some(date) => (String::from("Daily challenge"), date.clone()),
+            // The difficulty found, which is not the one asked for if no sequent of that difficulty was found
+            None => (format!("Random challenge #{}", challenge.seed), challenge.estimate.difficulty.to_string()),
         },
+        (None, None) => return,
+    };
+
+    {
+        // Draw level name
+        let text_pos = ScreenPosition { x: 0.0, y: LEVEL_NAME_Y }.to_pixel(gfx);
+        let mut text = draw.text(&state.text_font, &name);
+        text.position(text_pos.x, text_pos.y)
+            .color(state.settings.theme().ui_text)
+            .v_align_middle()
+            .h_align_center();
+    
+        set_text_size(&mut text, LEVEL_NAME_SIZE, gfx);
+    }
+
+    {
+        // Draw level index
+        let text_pos = ScreenPosition { x: 0.0, y: LEVEL_CHANGE_Y }.to_pixel(gfx);
+        let mut text = draw.text(&state.text_font, &index_text);
+        text.position(text_pos.x, text_pos.y)
+            .color(state.settings.theme().ui_text)
+            .v_align_middle()
+            .h_align_center();
+    
+        set_text_size(&mut text, LEVEL_CHANGE_SIZE, gfx);
     }
+
+    // The daily challenge can't be changed
+    if game_state.daily.is_some() {
+        return;
+    }
+
+    // Draw keys
+    draw_action(
+        crate::action::Action::Left,
+        ScreenPosition { x: -LEVEL_CHANGE_KEYS_X, y: LEVEL_CHANGE_Y }, 
+        1.0,
+        *state.settings.theme(), state.settings.bindings(),
+        &state.symbol_font, draw, gfx
+    );
+    draw_action(
+        crate::action::Action::Right,
+        ScreenPosition { x: LEVEL_CHANGE_KEYS_X, y: LEVEL_CHANGE_Y }, 
+        1.0,
+        *state.settings.theme(), state.settings.bindings(),
+        &state.symbol_font, draw, gfx
+    );
+}
+
+/// Seconds and hundredths, as displayed by the timer
+pub fn format_time(time: f32) -> String {
+    return format!("{}:{}", time.floor(), (time * 100.0).floor() as u32 % 100);
 }
 
 pub fn render_timer(finished: bool, draw: &mut Draw, gfx: &Graphics, state: &State)
@@ -195,7 +246,13 @@ pub fn render_timer(finished: bool, draw: &mut Draw, gfx: &Graphics, state: &Sta
     };
 
     let pos = ScreenPosition { x: -state.screen_ratio + TIMER_MARGIN, y: -1.0 + TIMER_MARGIN }.to_pixel(gfx);
-    let text = format!("{}:{}", time.floor(), (time * 100.0).floor() as u32 % 100);
+    let mut text = format_time(time);
+    if finished && game_state.hints_used > 0 {
+        text += " (assisted)";
+    }
+    if game_state.replayed {
+        text += " (replay)";
+    }
 
     {
         let mut text = draw.text(&state.text_font, &text);
@@ -208,6 +265,94 @@ pub fn render_timer(finished: bool, draw: &mut Draw, gfx: &Graphics, state: &Sta
     }
 }
 
+/// Result of the daily challenge, once solved. Shows the first solution if the puzzle was already solved today.
+pub fn render_daily_result(draw: &mut Draw, gfx: &Graphics, state: &State) {
+    let game_state = match &state.mode {
+        GameMode::Ingame(s) => s,
+        _ => unreachable!()
+    };
+
+    let Some(date) = &game_state.daily else { return };
+    let Some(result) = state.progress.get_daily(date) else { return };
+
+    let mut lines = vec![
+        format!("Daily challenge of {} solved!", date),
+        format!("Time: {}{}", format_time(result.time), if result.assisted { " (assisted)" } else { "" }),
+        format!("Rules: {}{}", result.rule_count, if result.raa_used { " (with RAA)" } else { "" }),
+    ];
+
+    let time = game_state.proof_finish_time - game_state.edit_start_time;
+    if time != result.time {
+        lines.push(format!("This time: {}, only the first solution counts", format_time(time)));
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        let pos = ScreenPosition { x: 0.0, y: DAILY_RESULT_Y - i as f32 * DAILY_RESULT_LINE_HEIGHT }.to_pixel(gfx);
+        let mut text = draw.text(&state.text_font, line);
+        text.position(pos.x, pos.y)
+            .color(state.settings.theme().ui_text)
+            .v_align_middle()
+            .h_align_center();
+
+        set_text_size(&mut text, DAILY_RESULT_SIZE, gfx);
+    }
+}
+
+/// Shown while the sequent of a generated level is searched for
+pub fn render_generating(draw: &mut Draw, gfx: &Graphics, state: &State) {
+    let game_state = match &state.mode {
+        GameMode::Ingame(s) => s,
+        _ => unreachable!()
+    };
+
+    let Some(generation) = &game_state.generating else { return };
+
+    let pos = ScreenPosition { x: 0.0, y: 0.0 }.to_pixel(gfx);
+    let mut text = draw.text(&state.text_font, &format!("Generating random challenge #{} ({})...", generation.seed, generation.difficulty));
+    text.position(pos.x, pos.y)
+        .color(state.settings.theme().ui_text)
+        .v_align_middle()
+        .h_align_center();
+
+    set_text_size(&mut text, GENERATING_SIZE, gfx);
+}
+
+pub fn render_truth_hint(draw: &mut Draw, gfx: &Graphics, state: &State) {
+    let game_state = match &state.mode {
+        GameMode::Ingame(s) => s,
+        _ => unreachable!()
+    };
+
+    let Some(hint) = &game_state.truth_hint else { return };
+
+    let pos = ScreenPosition { x: 0.0, y: TRUTH_HINT_Y }.to_pixel(gfx);
+    let mut text = draw.text(&state.symbol_font, hint);
+    text.position(pos.x, pos.y)
+        .color(state.settings.theme().ui_text)
+        .v_align_middle()
+        .h_align_center();
+
+    set_text_size(&mut text, TRUTH_HINT_SIZE, gfx);
+}
+
+pub fn render_hint(draw: &mut Draw, gfx: &Graphics, state: &State) {
+    let game_state = match &state.mode {
+        GameMode::Ingame(s) => s,
+        _ => unreachable!()
+    };
+
+    let Some(hint) = &game_state.hint_text else { return };
+
+    let pos = ScreenPosition { x: 0.0, y: HINT_Y }.to_pixel(gfx);
+    let mut text = draw.text(&state.symbol_font, hint);
+    text.position(pos.x, pos.y)
+        .color(state.settings.theme().ui_text)
+        .v_align_middle()
+        .h_align_center();
+
+    set_text_size(&mut text, HINT_SIZE, gfx);
+}
+
 fn draw_action_and_text(pos: ScreenPosition, action: crate::action::Action, text: &str, text_scale: f32, theme: Theme, bindings: &crate::action::Bindings, 
     symbol_font: &Font, draw: &mut Draw, gfx: &Graphics
 ) {
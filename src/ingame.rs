@@ -6,8 +6,13 @@ pub const SCREEN_SHAKE_AMPLITUDE: f32 = 0.01;
 
 pub const LEVEL_CHANGE_SEQUENT_SHIFT: f32 = 0.2;
 
+/// Folder where the proofs are exported
+pub const EXPORTS_PATH: &str = "exports";
+
 pub struct GameState {
     pub logic_system: LogicSystem,
+    /// Rules the level lets the player use
+    pub rules: RuleFilter,
     pub state: UndoState,
     pub undo_stack: Vec<UndoState>,
     pub redo_stack: Vec<UndoState>,
@@ -20,9 +25,48 @@ pub struct GameState {
     pub current_level_id: Option<usize>,
     pub edit_start_time: f32,
     pub proof_finish_time: f32,
+
+    /// Should the player be told if the current sequent is true?
+    pub show_truth_hint: bool,
+    /// Text of the truth hint for the current sequent, if it should be displayed
+    pub truth_hint: Option<String>,
+
+    /// Last hint asked, with the id of the node it is about
+    pub hint: Option<(u32, prover::Hint)>,
+    /// Should the formulas of the fields be suggested too?
+    pub show_hint_fields: bool,
+    /// Number of hints used on this level. A proof found with hints is assisted.
+    pub hints_used: u32,
+    /// Rule to highlight and text of the hint, if the player is on the node of the hint
+    pub hinted_rule: Option<u32>,
+    pub hint_text: Option<String>,
+
+    /// Ids of the new branches that still have to be checked for provability. One is checked per frame.
+    pub branches_to_check: Vec<u32>,
+
+    /// Actions of the player since the start of the level
+    pub recording: replay::Recording,
+    /// Set when a recorded solution is played back instead of reading the keyboard
+    pub replay: Option<replay::Replay>,
+    /// The proof comes from a replay, until the player presses a key. It isn't recorded as a solution or saved.
+    pub replayed: bool,
+
+    /// Set when playing a generated level instead of a level of a campaign
+    pub challenge: Option<generator::Challenge>,
+    /// Set while the generated level is searched for, which can take seconds
+    pub generating: Option<Generation>,
+    /// Date of the daily challenge being played, as YYYY-MM-DD
+    pub daily: Option<String>,
 }
 
-#[derive(Clone)]
+/// A generated level being searched for on another thread
+pub struct Generation {
+    pub seed: u32,
+    pub difficulty: Difficulty,
+    pub result: std::sync::mpsc::Receiver<Option<generator::Challenge>>,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct UndoState {
     pub proof: Proof,
     pub editing_formulas: bool,
@@ -37,6 +81,7 @@ pub struct UndoState {
     pub next_proof_index: u32,
 
     /// Creation times of the fields in the sequent, indexed by their id
+    #[serde(skip)]
     pub fields_creation_time: HashMap<u32, f32>,
     
     pub node_to_check_after_fields_completed: Option<u32>,
@@ -59,31 +104,89 @@ pub fn game_frame(state: &mut State, app: &App, gfx: &mut Graphics, draw: &mut D
 
     game_state.finished_proof = false;
 
+    // Nothing to play until the generated level is ready
+    receive_challenge(game_state, state.time);
+    if game_state.generating.is_some() {
+        game_ui::render_generating(draw, gfx, state);
+
+        if action::was_pressed(action::Action::Exit, state.settings.bindings(), app) {
+            state.mode = menus::get_in_menu(menus::main_menu(state));
+        }
+        return;
+    }
+
+    if action::was_pressed(action::Action::Replay, state.settings.bindings(), app) {
+        // The best solution, or the last one when the alternative key is down
+        let last_run = action::is_down(action::Action::SpecialRuleMode, state.settings.bindings(), app);
+
+        if !start_replay(game_state, &state.campaigns, &state.progress, last_run, state.time) {
+            screen_shake(game_state, app.timer.elapsed_f32());
+        }
+    }
+
+    // Actions that change the proof come from the replay if there is one, until the player takes over
+    let keyboard = replay::FrameInput::from_keyboard(state.settings.bindings(), app);
+    let input = replay::frame_input(&mut game_state.replay, &mut game_state.replayed, &mut game_state.recording, keyboard, state.time - game_state.edit_start_time);
+
     // Handle undo/redo
-    if action::was_pressed(action::Action::Undo, state.settings.bindings(), app) {
+    if input.was_pressed(action::Action::Undo) {
         if !undo(game_state) {
             screen_shake(game_state, app.timer.elapsed_f32());
         }
     }
-    else if action::was_pressed(action::Action::Redo, state.settings.bindings(), app) {
+    else if input.was_pressed(action::Action::Redo) {
         if !redo(game_state) {
             screen_shake(game_state, app.timer.elapsed_f32());
         }
     }
 
-    if action::was_pressed(action::Action::Restart, state.settings.bindings(), app) {
+    if action::was_pressed(action::Action::ToggleTruthHint, state.settings.bindings(), app) {
+        game_state.show_truth_hint = !game_state.show_truth_hint;
+    }
+
+    game_state.truth_hint = None;
+
+    if input.was_pressed(action::Action::Hint) {
+        ask_hint(game_state, app.timer.elapsed_f32());
+    }
+
+    if input.was_pressed(action::Action::Restart) {
         record_undo_entry(game_state);
         game_state.state = get_start_sequent_state(game_state.initial_sequent.clone(), app.timer.elapsed_f32());
     }
 
-    let special_mode = action::is_down(action::Action::SpecialRuleMode, state.settings.bindings(), app);
+    let special_mode = input.special_mode;
+
+    // Export the proof, with ebproofs instead of bussproofs when the alternative key is down
+    if action::was_pressed(action::Action::ExportLatex, state.settings.bindings(), app) {
+        let alternative = action::is_down(action::Action::SpecialRuleMode, state.settings.bindings(), app);
+        let style = if alternative { latex::LatexStyle::Ebproofs } else { latex::LatexStyle::Bussproofs };
+
+        match export_latex(game_state, style) {
+            Ok(path) => println!("Proof exported to {}", path),
+            Err(e) => {
+                println!("{}", e.message);
+                screen_shake(game_state, app.timer.elapsed_f32());
+            },
+        }
+    }
+
+    if action::was_pressed(action::Action::ExportSvg, state.settings.bindings(), app) {
+        match export_svg(game_state, &state.cached_sizes, state.settings.theme()) {
+            Ok(path) => println!("Proof exported to {}", path),
+            Err(e) => {
+                println!("{}", e.message);
+                screen_shake(game_state, app.timer.elapsed_f32());
+            },
+        }
+    }
 
     if game_state.state.editing_formulas {
         match game_state.state.formulas_position {
             Some(position) => {
                 // Check for operator insertion
                 for (i, op) in game_state.logic_system.operators.clone().into_iter().enumerate() {
-                    if action::was_pressed(action::Action::InsertOperator(i as u32), state.settings.bindings(), app) {
+                    if input.was_pressed(action::Action::InsertOperator(i as u32)) {
                         
                         record_undo_entry(game_state);
                         match proof::place_uncompleted_operator(op, position, &mut game_state.state.proof, &mut game_state.state.next_formula_index) {
@@ -97,7 +200,7 @@ pub fn game_frame(state: &mut State, app: &App, gfx: &mut Graphics, draw: &mut D
 
                 // Check for variable insertion
                 for i in 0..MAX_VARIABLE_COUNT {
-                    if action::was_pressed(action::Action::InsertVariable(i), state.settings.bindings(), app) {
+                    if input.was_pressed(action::Action::InsertVariable(i)) {
                         
                         record_undo_entry(game_state);
                         match proof::place_variable(i, position, &mut game_state.state.proof) {
@@ -112,12 +215,12 @@ pub fn game_frame(state: &mut State, app: &App, gfx: &mut Graphics, draw: &mut D
                 }
 
                 // Previous and next fields
-                if action::was_pressed(action::Action::NextField, state.settings.bindings(), app) {
+                if input.was_pressed(action::Action::NextField) {
                     game_state.state.formulas_position = Some(proof::formula_as_field(
                         proof::search_fields_by_id_in_proof(&mut game_state.state.proof, Some(position))[0]
                     ).next_id);
                 }
-                if action::was_pressed(action::Action::PreviousField, state.settings.bindings(), app) {
+                if input.was_pressed(action::Action::PreviousField) {
                     game_state.state.formulas_position = Some(proof::formula_as_field(
                         proof::search_fields_by_id_in_proof(&mut game_state.state.proof, Some(position))[0]
                     ).prev_id);
@@ -139,9 +242,13 @@ pub fn game_frame(state: &mut State, app: &App, gfx: &mut Graphics, draw: &mut D
             Some(current_proof) => {
                 current_proof.last_focused_time = app.timer.elapsed_f32();
 
+                if game_state.show_truth_hint {
+                    game_state.truth_hint = get_truth_hint(&current_proof.root);
+                }
+
                 // Check for rules insertion
                 for i in 0..game_state.logic_system.rules.len() {
-                    if action::was_pressed(action::Action::InsertRule(i as u32), state.settings.bindings(), app) {
+                    if input.was_pressed(action::Action::InsertRule(i as u32)) {
 
                         let rule = if special_mode {
                             match &game_state.logic_system.special_rules[i] {
@@ -153,6 +260,11 @@ pub fn game_frame(state: &mut State, app: &App, gfx: &mut Graphics, draw: &mut D
                             &game_state.logic_system.rules[i]
                         };
 
+                        if !game_state.rules.allows(rule.display_text()) {
+                            screen_shake(game_state, app.timer.elapsed_f32());
+                            break;
+                        }
+
                         let (branches, field_count) = rule.as_ref().create_branches(&current_proof.root);
                         match branches {
                             Some(new_branches) => {
@@ -165,6 +277,10 @@ pub fn game_frame(state: &mut State, app: &App, gfx: &mut Graphics, draw: &mut D
 
                                 game_state.state.node_to_check_after_fields_completed = Some(current_proof.id);
 
+                                if field_count == 0 {
+                                    game_state.branches_to_check.extend(current_proof.branches.iter().map(|b| b.id));
+                                }
+
                                 add_undo_entry(undo_entry, game_state);
                             },
                             None => {
@@ -184,19 +300,40 @@ pub fn game_frame(state: &mut State, app: &App, gfx: &mut Graphics, draw: &mut D
                 }
             },
             None => {
-                game_state.finished_proof = true;
-
                 if game_state.proof_finish_time == f32::NEG_INFINITY {
-                    game_state.proof_finish_time = state.time;
+                    // Check everything again before accepting the proof
+                    match proof::verify_proof(&game_state.state.proof, &game_state.logic_system) {
+                        Ok(()) => {
+                            game_state.proof_finish_time = state.time;
+                            record_progress(game_state, &state.campaigns, &mut state.progress);
+                        },
+                        Err(errors) => {
+                            let mut new_error = false;
+                            for e in errors {
+                                let node = get_proof_node_by_id(&mut game_state.state.proof, e.node_id).unwrap();
+                                new_error |= !node.is_rule_invalid;
+                                node.is_rule_invalid = true;
+                            }
+
+                            if new_error {
+                                screen_shake(game_state, app.timer.elapsed_f32());
+                            }
+                        },
+                    }
                 }
+
+                game_state.finished_proof = game_state.proof_finish_time != f32::NEG_INFINITY;
             }
         }
     }
 
+    update_hint_display(game_state);
+    check_next_branch(game_state);
+
     // Draw the proof
     let shake_delta = get_shake_delta_position(game_state, app.timer.elapsed_f32());
 
-    let mut render_info = proof::rendering::RenderInfo {
+    let mut render_info = rendering::RenderInfo {
         draw,
         gfx,
         text_font: &state.text_font,
@@ -242,24 +379,46 @@ pub fn game_frame(state: &mut State, app: &App, gfx: &mut Graphics, draw: &mut D
     }
 
     game_ui::render_timer(game_state.finished_proof, draw, gfx, state);
+    if game_state.finished_proof {
+        game_ui::render_daily_result(draw, gfx, state);
+    }
+    game_ui::render_truth_hint(draw, gfx, state);
+    game_ui::render_hint(draw, gfx, state);
     
     if action::was_pressed(action::Action::Exit, state.settings.bindings(), app) { // Handle exit key 
+        save_level_progress(game_state, state.time);
         state.mode = menus::get_in_menu(menus::main_menu(state));
     }
     else if action::was_pressed(action::Action::Left, state.settings.bindings(), app) { // Handle level change
-        if game_state.current_level_id.is_some() {
+        if game_state.daily.is_some() {
+            // The puzzle of the day can't be changed
+        }
+        else if let Some(challenge) = &game_state.challenge {
+            let seed = challenge.seed.wrapping_sub(1);
+            state.mode = get_challenge_state(seed, &challenge.difficulty, ScreenSize { x: -LEVEL_CHANGE_SEQUENT_SHIFT, y:0.0 }, state);
+        }
+        else if game_state.current_level_id.is_some() {
             let id = game_state.current_level_id.unwrap();
 
             if id > 0 {
+                save_level_progress(game_state, state.time);
                 state.mode = ingame::get_initial_state(game_state.current_campaign_id.as_deref(), Some(id - 1) , ScreenSize { x: -LEVEL_CHANGE_SEQUENT_SHIFT, y:0.0 }, state)
             }
         }
     }
     else if action::was_pressed(action::Action::Right, state.settings.bindings(), app) {  
-        if game_state.current_level_id.is_some() {
+        if game_state.daily.is_some() {
+            // The puzzle of the day can't be changed
+        }
+        else if let Some(challenge) = &game_state.challenge {
+            let seed = challenge.seed.wrapping_add(1);
+            state.mode = get_challenge_state(seed, &challenge.difficulty, ScreenSize { x: LEVEL_CHANGE_SEQUENT_SHIFT, y:0.0 }, state);
+        }
+        else if game_state.current_level_id.is_some() {
             let id = game_state.current_level_id.unwrap();
 
             if id < state.campaigns.get(game_state.current_campaign_id.as_ref().unwrap()).unwrap().levels.len() - 1{
+                save_level_progress(game_state, state.time);
                 state.mode = ingame::get_initial_state(game_state.current_campaign_id.as_deref(), Some(id + 1) , ScreenSize { x: LEVEL_CHANGE_SEQUENT_SHIFT, y:0.0 }, state)
             }
         }
@@ -385,16 +544,18 @@ fn adjust_proof_position(screen_ratio: f32, proof_width: f32, game_state: &mut G
 }
 
 pub fn get_initial_state(campaign_id: Option<&str>, level_id: Option<usize>, sequent_initial_position: ScreenSize, state: &State) -> GameMode {
-    let start_seq = match (campaign_id, level_id) {
+    let (start_seq, rules) = match (campaign_id, level_id) {
         (Some(campaign_id), Some(i)) => {
-            state.campaigns.get(campaign_id).unwrap().levels[i].seq.clone()
+            let level = &state.campaigns.get(campaign_id).unwrap().levels[i];
+            (level.seq.clone(), level.rules.clone())
         },
-        (None, None) => proof::get_empty_sequent(),
+        (None, None) => (proof::get_empty_sequent(), RuleFilter::default()),
         _ => unreachable!(),
     };
 
-    return GameMode::Ingame(ingame::GameState {
+    let mut game_state = ingame::GameState {
         logic_system: proof::natural_logic::get_system(),
+        rules,
         undo_stack: Vec::new(),
         redo_stack: Vec::new(),
         state: get_start_sequent_state(start_seq.clone(), state.time),
@@ -407,7 +568,190 @@ pub fn get_initial_state(campaign_id: Option<&str>, level_id: Option<usize>, seq
         current_level_id: level_id,
         edit_start_time: state.time,
         proof_finish_time: f32::NEG_INFINITY,
+        show_truth_hint: false,
+        truth_hint: None,
+        hint: None,
+        show_hint_fields: false,
+        hints_used: 0,
+        hinted_rule: None,
+        hint_text: None,
+        branches_to_check: Vec::new(),
+        recording: Vec::new(),
+        replay: None,
+        replayed: false,
+        challenge: None,
+        generating: None,
+        daily: None,
+    };
+
+    if let (Some(campaign_id), Some(i)) = (campaign_id, level_id) {
+        restore_level_progress(&mut game_state, campaign_id, i, state.time);
+    }
+
+    return GameMode::Ingame(game_state);
+}
+
+/// Generated level of the seed. The sequent is searched for on another thread, so that the window doesn't freeze.
+pub fn get_challenge_state(seed: u32, difficulty: &Difficulty, sequent_initial_position: ScreenSize, state: &State) -> GameMode {
+    let mut mode = get_initial_state(None, None, sequent_initial_position, state);
+    let GameMode::Ingame(game_state) = &mut mode else { unreachable!() };
+
+    let (sender, result) = std::sync::mpsc::channel();
+    let thread_difficulty = difficulty.clone();
+    std::thread::spawn(move || {
+        // The receiver is gone if the player left before the end
+        let _ = sender.send(generator::random_challenge(seed, &thread_difficulty));
     });
+
+    game_state.generating = Some(Generation { seed, difficulty: difficulty.clone(), result });
+
+    return mode;
+}
+
+/// Starts the generated level once it is found. Starts free editing if no provable sequent was found.
+fn receive_challenge(game_state: &mut GameState, time: f32) {
+    let Some(generation) = &game_state.generating else { return };
+
+    let challenge = match generation.result.try_recv() {
+        Ok(challenge) => challenge,
+        Err(std::sync::mpsc::TryRecvError::Empty) => return,
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => None,
+    };
+
+    match challenge {
+        Some(challenge) => {
+            game_state.state = get_start_sequent_state(challenge.seq.clone(), time);
+            game_state.initial_sequent = challenge.seq.clone();
+            game_state.challenge = Some(challenge);
+        },
+        None => {
+            println!("No sequent found for the random challenge {}", generation.seed);
+            game_state.daily = None;
+        },
+    }
+
+    // The timer starts with the level
+    game_state.edit_start_time = time;
+    game_state.generating = None;
+}
+
+/// Puzzle of the day, the same for every player
+pub fn get_daily_state(state: &State) -> GameMode {
+    let date = date::current_date();
+    let mut mode = get_challenge_state(generator::daily_seed(&date), &generator::DAILY_DIFFICULTY, ScreenSize::zero(), state);
+    let GameMode::Ingame(game_state) = &mut mode else { unreachable!() };
+
+    game_state.daily = Some(date);
+
+    return mode;
+}
+
+/// Restarts the level and plays a recorded solution back. Returns false if there is none.
+fn start_replay(game_state: &mut GameState, campaigns: &HashMap<String, Campaign>, progress: &progress::Progress, last_run: bool, time: f32) -> bool {
+    let (Some(campaign_id), Some(i)) = (&game_state.current_campaign_id, game_state.current_level_id) else { return false };
+
+    let recording = match progress.get_level(campaign_id, campaigns.get(campaign_id).unwrap().levels[i].id) {
+        Some(level) if last_run => level.last_run.clone(),
+        Some(level) => level.best_run.clone().unwrap_or_else(|| level.last_run.clone()),
+        None => return false,
+    };
+
+    if recording.is_empty() {
+        return false;
+    }
+
+    // The proof in progress is kept in the save of the level
+    save_level_progress(game_state, time);
+
+    game_state.state = get_start_sequent_state(game_state.initial_sequent.clone(), time);
+    game_state.undo_stack = Vec::new();
+    game_state.redo_stack = Vec::new();
+    game_state.edit_start_time = time;
+    game_state.proof_finish_time = f32::NEG_INFINITY;
+    game_state.hint = None;
+    game_state.show_hint_fields = false;
+    game_state.hints_used = 0;
+    game_state.branches_to_check = Vec::new();
+    game_state.recording = Vec::new();
+    game_state.replay = Some(replay::Replay::new(recording));
+    game_state.replayed = true;
+
+    return true;
+}
+
+/// Adds the finished proof to the records of the level, or of the daily challenge
+fn record_progress(game_state: &GameState, campaigns: &HashMap<String, Campaign>, progress: &mut progress::Progress) {
+    if game_state.replayed {
+        return;
+    }
+
+    let solution = progress::Solution::from_proof(
+        &game_state.state.proof,
+        game_state.proof_finish_time - game_state.edit_start_time,
+        game_state.hints_used > 0,
+        game_state.recording.clone()
+    );
+
+    match (&game_state.current_campaign_id, game_state.current_level_id, &game_state.daily, &game_state.challenge) {
+        (Some(campaign_id), Some(i), _, _) => {
+            progress.record_solution(campaign_id, campaigns.get(campaign_id).unwrap().levels[i].id, &solution, &date::current_date());
+        },
+        (_, _, Some(date), Some(challenge)) => {
+            if !progress.record_daily(date, challenge.seed, &solution) {
+                return;
+            }
+        },
+        _ => return,
+    }
+
+    if let Err(e) = progress::save_progress(progress) {
+        println!("{}", e.message);
+    }
+}
+
+/// Keeps the proof of the current level, so that it is restored when the level is reopened
+fn save_level_progress(game_state: &GameState, time: f32) {
+    let (Some(campaign_id), Some(level_id)) = (&game_state.current_campaign_id, game_state.current_level_id) else { return };
+
+    // A replay is not the work of the player
+    if game_state.replayed {
+        return;
+    }
+
+    // Nothing worth keeping if the proof is untouched or finished.
+    // finished_proof is only set at the end of the frame, so the finish time is used.
+    let result = if game_state.proof_finish_time != f32::NEG_INFINITY || game_state.undo_stack.is_empty() {
+        saves::delete_level_save(campaign_id, level_id)
+    } else {
+        saves::save_level(campaign_id, level_id, &saves::LevelSave {
+            initial_sequent: game_state.initial_sequent.clone(),
+            state: game_state.state.clone(),
+            undo_stack: game_state.undo_stack.clone(),
+            redo_stack: game_state.redo_stack.clone(),
+            elapsed_time: time - game_state.edit_start_time,
+            hints_used: game_state.hints_used,
+            recording: game_state.recording.clone(),
+        })
+    };
+
+    if let Err(e) = result {
+        println!("{}", e.message);
+    }
+}
+
+fn restore_level_progress(game_state: &mut GameState, campaign_id: &str, level_id: usize, time: f32) {
+    match saves::load_level(campaign_id, level_id) {
+        Ok(Some(save)) if save.initial_sequent == game_state.initial_sequent => {
+            game_state.state = save.state;
+            game_state.undo_stack = save.undo_stack;
+            game_state.redo_stack = save.redo_stack;
+            game_state.edit_start_time = time - save.elapsed_time;
+            game_state.hints_used = save.hints_used;
+            game_state.recording = save.recording;
+        },
+        Ok(_) => { },
+        Err(e) => println!("{}", e.message),
+    }
 }
 
 fn get_start_sequent_state(s: Sequent, time: f32) -> UndoState {
@@ -440,6 +784,156 @@ fn get_start_sequent_state(s: Sequent, time: f32) -> UndoState {
 }
 
 
+pub struct ExportError {
+    pub message: String,
+}
+
+/// Path of the exported proof, named after the level
+fn export_path(game_state: &GameState, extension: &str) -> String {
+    let name = match (&game_state.current_campaign_id, game_state.current_level_id) {
+        (Some(campaign_id), Some(level_id)) => {
+            format!("{}_{}", saves::campaign_file_name(campaign_id), level_id + 1)
+        },
+        _ => match (&game_state.daily, &game_state.challenge) {
+            (Some(date), _) => format!("daily_{}", date),
+            (None, Some(challenge)) => format!("challenge_{}", challenge.seed),
+            (None, None) => String::from("free_editing"),
+        },
+    };
+
+    return format!("{}/{}.{}", EXPORTS_PATH, name, extension);
+}
+
+fn write_export(path: &str, text: String) -> Result<(), ExportError> {
+    std::fs::create_dir_all(EXPORTS_PATH)
+        .and_then(|_| std::fs::write(path, text))
+        .map_err(|e| ExportError { message: format!("Error while exporting the proof to {}: {}", path, e) })
+}
+
+/// Writes the proof in a .tex file named after the level. Returns the path of the file.
+fn export_latex(game_state: &GameState, style: latex::LatexStyle) -> Result<String, ExportError> {
+    let path = export_path(game_state, "tex");
+    write_export(&path, latex::latex_document(&game_state.state.proof, &game_state.logic_system, style))?;
+
+    return Ok(path);
+}
+
+/// Writes the proof as drawn in game in a .svg file named after the level. Returns the path of the file.
+fn export_svg(game_state: &GameState, char_widths: &HashMap<char, f32>, theme: &settings::Theme) -> Result<String, ExportError> {
+    let color = |c: Color| [c.r, c.g, c.b, c.a];
+    let svg_theme = svg::SvgTheme {
+        background: color(theme.bg),
+        text: color(theme.seq_text),
+        bar: color(theme.seq_bar),
+        invalid: color(theme.seq_invalid),
+        unprovable: color(theme.seq_unprovable),
+        field: color(theme.seq_field),
+    };
+
+    let path = export_path(game_state, "svg");
+    write_export(&path, svg::proof_to_svg(&game_state.state.proof, &game_state.logic_system, char_widths, &svg_theme))?;
+
+    return Ok(path);
+}
+
+
+/// Tells if the sequent is true, or gives a valuation that makes it false
+fn get_truth_hint(s: &Sequent) -> Option<String> {
+    let table = truth_table::truth_table(s)?;
+
+    match table.falsifying_rows().first() {
+        None => Some(String::from("This sequent is true")),
+        Some(row) => {
+            let values: Vec<String> = table.variables.iter().map(|v| {
+                format!("{} = {}", 
+                    proof::variable_name(&s.variables, *v), 
+                    get_operator_symbol(if row.valuation[v] { OperatorType::Top } else { OperatorType::Bottom })
+                )
+            }).collect();
+
+            Some(format!("This sequent is false when {}", values.join(", ")))
+        },
+    }
+}
+
+
+/// Node the player is working on: the one whose fields are being completed, or the first unfinished one
+fn get_working_node(state: &mut UndoState) -> Option<&mut Proof> {
+    if state.editing_formulas {
+        return get_proof_node_by_id(&mut state.proof, state.node_to_check_after_fields_completed?);
+    }
+    return proof::get_first_unfinished_proof(&mut state.proof);
+}
+
+/// The first time, suggests a rule for the current node. The second time, also suggests the formulas of the fields.
+fn ask_hint(game_state: &mut GameState, time: f32) {
+    let Some(node) = get_working_node(&mut game_state.state) else {
+        screen_shake(game_state, time);
+        return;
+    };
+    let node_id = node.id;
+
+    match &game_state.hint {
+        Some((hint_node_id, _)) if *hint_node_id == node_id => {
+            if !game_state.show_hint_fields {
+                game_state.show_hint_fields = true;
+                game_state.hints_used += 1;
+            }
+        },
+        _ => {
+            match prover::get_hint(&node.root, &game_state.rules) {
+                Some(hint) => {
+                    game_state.hint = Some((node_id, hint));
+                    game_state.show_hint_fields = false;
+                    game_state.hints_used += 1;
+                },
+                None => {
+                    // Nothing can be done from here
+                    screen_shake(game_state, time);
+                },
+            }
+        },
+    }
+}
+
+fn update_hint_display(game_state: &mut GameState) {
+    game_state.hinted_rule = None;
+    game_state.hint_text = None;
+
+    let Some((hint_node_id, hint)) = &game_state.hint else { return };
+    let Some(node) = get_working_node(&mut game_state.state) else { return };
+
+    if node.id != *hint_node_id {
+        return;
+    }
+
+    let rule_text = game_state.logic_system.rules[hint.rule_id as usize].display_text();
+    let fields: Vec<String> = hint.fields.iter().map(|f| proof::text::formula_text(f, &node.root.variables, proof::text::Symbols::Unicode)).collect();
+
+    game_state.hinted_rule = Some(hint.rule_id);
+    game_state.hint_text = Some(if game_state.show_hint_fields && !fields.is_empty() {
+        format!("Try the rule {} with {}", rule_text, fields.join(", "))
+    } else {
+        format!("Try the rule {}", rule_text)
+    });
+}
+
+
+/// Marks the next branch in the queue if it can't be proven anymore
+fn check_next_branch(game_state: &mut GameState) {
+    let Some(id) = game_state.branches_to_check.pop() else { return };
+
+    // Without RAA, only the intuitionistic sequents can be proven
+    let raa = game_state.logic_system.rules[natural_logic::RAA_RULE as usize].display_text();
+    let is_provable = if game_state.rules.allows(raa) { calcul::is_provable } else { intuitionistic::is_provable };
+
+    // The node may have disappeared with an undo
+    if let Some(node) = get_proof_node_by_id(&mut game_state.state.proof, id) {
+        node.is_unprovable = is_provable(&node.root) == Some(false);
+    }
+}
+
+
 /// Exists formula mode, and check that the fields are correct for the new rule. 
 fn exit_formula_mode(game_state: &mut GameState, time: f32) {
     match &mut game_state.state.node_to_check_after_fields_completed {
@@ -448,6 +942,7 @@ fn exit_formula_mode(game_state: &mut GameState, time: f32) {
             let ok = game_state.logic_system.rules[proof.rule_id.unwrap() as usize].check_validity(proof);
 
             if ok {
+                game_state.branches_to_check.extend(proof.branches.iter().map(|b| b.id));
                 game_state.state.node_to_check_after_fields_completed = None;
                 game_state.state.editing_formulas = false; // Correct -> exit formula mode
             }
@@ -458,6 +953,14 @@ fn exit_formula_mode(game_state: &mut GameState, time: f32) {
                 screen_shake(game_state, time);
             }
         },
-        None => game_state.state.editing_formulas = false, // Nothing to check -> exit formula mode
+        None => {
+            game_state.state.editing_formulas = false; // Nothing to check -> exit formula mode
+
+            // The player just wrote the sequent to prove: warn if it can't be proven
+            if game_state.state.proof.rule_id.is_none() && calcul::is_provable(&game_state.state.proof.root) == Some(false) {
+                game_state.state.proof.is_rule_invalid = true;
+                screen_shake(game_state, time);
+            }
+        },
     }
 }
@@ -3,6 +3,7 @@
 
 use std::collections::HashMap;
 
+use sequent_engine::{parser, proof, generator, date};
 use proof::*;
 use calcul::*;
 use coord::*;
@@ -10,10 +11,10 @@ use notan::prelude::*;
 use notan::draw::*;
 use notan::egui::{self, *};
 use rendering::draw_proof;
-use rendering::get_proof_width;
+use layout::get_proof_width;
 use crate::parser::*;
 
-mod proof;
+mod rendering;
 mod coord;
 mod action;
 mod game_ui;
@@ -22,8 +23,10 @@ mod animation;
 mod ingame;
 mod menus;
 mod settings;
+mod saves;
+mod progress;
+mod replay;
 mod misc;
-mod parser;
 
 pub const LEVELS_PATH: &str = "assets/levels";
 
@@ -55,6 +58,7 @@ struct State {
     screen_ratio: f32,
     background_state: background::BackgroundState,
     settings: settings::Settings,
+    progress: progress::Progress,
     campaigns: HashMap<String, Campaign>,
     time: f32,
 }
@@ -71,8 +75,6 @@ pub const CAMERA_MOVEMENT_SPEED_SCALE: f32 = 7.0;
 
 #[notan_main]
 fn main() -> Result<(), String> {
-    // calculation_test();
-
     // Get backtraces
     std::env::set_var("RUST_BACKTRACE", "1");
 
@@ -108,24 +110,60 @@ fn setup(gfx: &mut Graphics) -> State {
         },
     };
 
+    let progress = match progress::load_progress() {
+        Ok(p) => p,
+        Err(e) => {
+            panic!("{}", e.message);
+        },
+    };
+
+    let mut files: Vec<std::path::PathBuf> = match std::fs::read_dir(LEVELS_PATH) {
+        Ok(files) => files.filter_map(|f| f.ok()).map(|f| f.path()).collect(),
+        Err(e) => {
+            println!("Couldn't read the levels folder {}: {}", LEVELS_PATH, e);
+            Vec::new()
+        },
+    };
+    // A campaign converted to .ron is preferred to the .sq file it was made from, which has the same name
+    files.sort_by_key(|path| (path.extension().is_none_or(|ext| ext != "ron"), path.clone()));
+
     let mut campaigns = HashMap::new();
-    for file in std::fs::read_dir(LEVELS_PATH).unwrap() {
-        campaigns.insert(
-            String::from(file.as_ref().unwrap().path().file_name().unwrap().to_str().unwrap()),
-            parse_file(file.as_ref().unwrap().path().to_str().unwrap())
-        );
+    for path in files {
+        let mut campaign = match parse_file(&path.to_string_lossy()) {
+            Ok(campaign) => campaign,
+            Err(e) => {
+                println!("{}", e.message);
+                continue;
+            },
+        };
+
+        for e in campaign.errors.iter() {
+            println!("Level skipped: {}", e);
+        }
+
+        for warning in check_campaign(&mut campaign) {
+            println!("{}", warning);
+        }
+
+        if campaigns.contains_key(&campaign.name) {
+            println!("{} skipped: the campaign {} is already loaded", path.display(), campaign.name);
+            continue;
+        }
+
+        campaigns.insert(campaign.name.clone(), campaign);
     }
 
     let mut state = State {
         text_font: font,
         symbol_font,
-        cached_sizes: proof::rendering::compute_char_sizes(&font, &symbol_font),
+        cached_sizes: rendering::compute_char_sizes(&font, &symbol_font),
         mode: GameMode::None,
         // mode: ingame::get_initial_state(proof::get_empty_sequent(), 0.0),
         campaigns,
         screen_ratio: 1.0,
         background_state: background::init_background_state(),
         settings,
+        progress,
         time: 0.0,
     };
 
@@ -190,21 +228,3 @@ fn draw(app: &mut App, gfx: &mut Graphics, plugins: &mut Plugins, state: &mut St
     gfx.render(&ui_output);
     gfx.render(&draw);
 }
-
-fn calculation_test() {
-    let seq = Sequent {
-        before: vec![],
-        after: vec![
-            Formula::Operator(Operator {
-                operator_type: OperatorType::Or,
-                arg1: Some(Box::new(Formula::Operator(Operator {
-                    operator_type: OperatorType::Not,
-                    arg1: Some(Box::new(Formula::Variable(1))), arg2: None }))),
-                    arg2: Some(Box::new(Formula::Variable(1))),
-            })
-        ],
-    };
-    let test = proof_or_fake(seq);
-
-    println!("seq = {}", test);
-}
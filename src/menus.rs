@@ -1,5 +1,6 @@
 
 
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
@@ -7,7 +8,7 @@ use notan::draw::{Draw, DrawShapes, DrawTextSection};
 use notan::app::Graphics;
 use notan::prelude::*;
 use crate::coord::PixelPosition;
-use crate::{action, ingame, misc, proof};
+use crate::{action, generator, ingame, misc, proof};
 use crate::State;
 use crate::coord::*;
 
@@ -31,6 +32,8 @@ pub const LEVEL_SELECTION_DIFFICULTY_Y: f32 = 0.115;
 pub const LEVEL_SELECTION_SEQUENT_Y: f32 = 0.02;
 pub const LEVEL_SELECTION_MARGIN_AFTER_NAME: f32 = 0.04;
 pub const LEVEL_SELECTION_DIFFICULTY_TEXT_SIZE: f32 = 20.0;
+pub const LEVEL_SELECTION_PROGRESS_MARGIN: f32 = 0.05;
+pub const LEVEL_SELECTION_BESTS_Y: f32 = 0.03;
 
 pub const MENU_LEFT_SPACE: f32 = 0.2;
 pub const MENUS_BASE_Y: f32 = 0.1;
@@ -105,6 +108,8 @@ pub struct LevelSelection {
     pub level: crate::parser::Level,
     pub id: usize,
     pub campaign_id: String,
+    /// Records of the player, if the level is solved
+    pub progress: Option<crate::progress::LevelProgress>,
     pub last_focused_time: f32,
     pub last_unfocused_time: f32,
 }
@@ -113,6 +118,13 @@ pub struct Label {
     pub label: String,
 }
 
+/// Number typed with the digit keys while focused. Shared with the buttons that use it.
+pub struct SeedInput {
+    pub seed: Rc<Cell<Option<u32>>>,
+    pub last_focused_time: f32,
+    pub last_unfocused_time: f32,
+}
+
 impl MenuItem for Button {
     fn draw(&mut self, bottom_left: ScreenPosition, focused: bool, info: &mut DrawInfo) {
         handle_focus_times(&mut self.last_focused_time, &mut self.last_unfocused_time, focused, info);
@@ -182,6 +194,66 @@ impl MenuItem for Label {
     }
 }
 
+impl MenuItem for SeedInput {
+    fn draw(&mut self, bottom_left: ScreenPosition, focused: bool, info: &mut DrawInfo) {
+        handle_focus_times(&mut self.last_focused_time, &mut self.last_unfocused_time, focused, info);
+
+        if focused {
+            const DIGITS: [KeyCode; 10] = [
+                KeyCode::Key0, KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4,
+                KeyCode::Key5, KeyCode::Key6, KeyCode::Key7, KeyCode::Key8, KeyCode::Key9,
+            ];
+
+            for (digit, key) in DIGITS.iter().enumerate() {
+                if info.app.keyboard.was_pressed(*key) {
+                    let seed = self.seed.get().unwrap_or(0);
+                    self.seed.set(Some(seed.checked_mul(10).and_then(|s| s.checked_add(digit as u32)).unwrap_or(seed)));
+                }
+            }
+
+            if info.app.keyboard.was_pressed(KeyCode::Back) {
+                self.seed.set(self.seed.get().map(|s| s / 10).filter(|s| *s != 0));
+            }
+        }
+
+        let size = ScreenSize { x: BUTTONS_WIDTH, y: BUTTON_HEIGHT };
+
+        let bg_color = get_bg_color(self.last_focused_time, self.last_unfocused_time, focused, info);
+        let left_padding = get_left_padding(self.last_focused_time, self.last_unfocused_time, focused, info);
+
+        info.draw.rect(bottom_left.to_pixel(info.gfx).as_couple(), size.to_pixel(info.gfx))
+            .color(bg_color);
+
+        let mut text_pos = bottom_left;
+        text_pos.x += left_padding;
+        text_pos.y += BUTTON_HEIGHT * 0.5;
+
+        let label = match self.seed.get() {
+            Some(seed) => format!("Seed: {}", seed),
+            None => "Seed: random".to_string(),
+        };
+        let mut text = info.draw.text(info.text_font, &label);
+            
+        text.position(text_pos.to_pixel(info.gfx).x, text_pos.to_pixel(info.gfx).y)
+            .v_align_middle()
+            .h_align_left();
+
+        set_text_size(&mut text, TEXT_SIZE, info.gfx);
+    }
+
+    fn on_interact(&mut self) { }
+
+    fn get_effect(&self) -> MenuEffect { MenuEffect::Nothing }
+
+    fn get_height(&self, _info: &mut DrawInfo) -> f32 {
+        BUTTON_HEIGHT
+    }
+
+    fn get_focusable(&self) -> bool {
+        true
+    }
+}
+
 impl MenuItem for LevelSelection {
     fn draw(&mut self, bottom_left: ScreenPosition, focused: bool, info: &mut DrawInfo) {
         handle_focus_times(&mut self.last_focused_time, &mut self.last_unfocused_time, focused, info);
@@ -216,7 +288,11 @@ impl MenuItem for LevelSelection {
         text_pos.y += LEVEL_SELECTION_DIFFICULTY_Y - LEVEL_SELECTION_NAME_Y;
 
         {
-            let diff_string = format!("({})", self.level.difficulty.to_string());
+            let diff_string = if self.level.classical_only {
+                format!("({}, classical only)", self.level.difficulty.to_string())
+            } else {
+                format!("({})", self.level.difficulty.to_string())
+            };
             let mut text = info.draw.text(info.text_font, &diff_string);
             
             text.position(text_pos.to_pixel(info.gfx).x, text_pos.to_pixel(info.gfx).y)
@@ -227,11 +303,47 @@ impl MenuItem for LevelSelection {
             set_text_size(&mut text, LEVEL_SELECTION_DIFFICULTY_TEXT_SIZE, info.gfx);
         }
 
+        // Checkmark and personal bests
+        if let Some(progress) = &self.progress {
+            let mut progress_pos = bottom_left;
+            progress_pos.x += LEVEL_SELECTION_WIDTH - LEVEL_SELECTION_PROGRESS_MARGIN;
+            progress_pos.y += LEVEL_SELECTION_NAME_Y;
+
+            {
+                let mut text = info.draw.text(info.symbol_font, "✓");
+
+                text.position(progress_pos.to_pixel(info.gfx).x, progress_pos.to_pixel(info.gfx).y)
+                    .v_align_bottom()
+                    .h_align_right();
+
+                set_text_size(&mut text, TEXT_SIZE, info.gfx);
+            }
+
+            let bests_string = match (progress.best_time, progress.fewest_rules) {
+                (Some(time), Some(rules)) => format!("Best: {}, {} rules", crate::game_ui::format_time(time), rules),
+                _ => String::from("Solved with hints"),
+            };
+            let bests_string = if progress.raa_used { format!("{}, with RAA", bests_string) } else { bests_string };
+
+            progress_pos.y = bottom_left.y + LEVEL_SELECTION_BESTS_Y;
+
+            {
+                let mut text = info.draw.text(info.text_font, &bests_string);
+
+                text.position(progress_pos.to_pixel(info.gfx).x, progress_pos.to_pixel(info.gfx).y)
+                    .v_align_bottom()
+                    .h_align_right()
+                    .color(info.theme.ui_text_dark);
+
+                set_text_size(&mut text, LEVEL_SELECTION_DIFFICULTY_TEXT_SIZE, info.gfx);
+            }
+        }
+
         let mut sequent_pos = bottom_left;
         sequent_pos.x += left_padding;
         sequent_pos.y += LEVEL_SELECTION_SEQUENT_Y;
 
-        let mut render_info = proof::rendering::RenderInfo {
+        let mut render_info = crate::rendering::RenderInfo {
             draw: info.draw,
             gfx: info.gfx,
             text_font: info.text_font,
@@ -247,7 +359,7 @@ impl MenuItem for LevelSelection {
             fields_creation_time: &mut HashMap::new(),
         };
 
-        proof::rendering::draw_sequent(&self.level.seq, sequent_pos, 1.0, &mut render_info);
+        crate::rendering::draw_sequent(&self.level.seq, sequent_pos, 1.0, &mut render_info);
     }
 
     fn on_interact(&mut self) {
@@ -406,11 +518,18 @@ fn label(label: &str) -> Box<Label> {
     });
 }
 
-pub fn main_menu(_: &State) -> Menu {
+pub fn main_menu(state: &State) -> Menu {
+    let daily_label = match state.progress.get_daily(&crate::date::current_date()) {
+        Some(_) => "Daily challenge (solved)",
+        None => "Daily challenge",
+    };
+
     return Menu { 
         elements: vec![
             button("Solve", MenuEffect::ChangeMenu(Rc::new(campaigns_menu))),
+            button(daily_label, MenuEffect::ChangeGameMode(Rc::new(ingame::get_daily_state))),
             button("Free editing", MenuEffect::ChangeGameMode(Rc::new(start_free_editing))),
+            button("Random challenge", MenuEffect::ChangeMenu(Rc::new(challenge_menu))),
             button("Settings", MenuEffect::ChangeMenu(Rc::new(settings))),
             button("Quit", MenuEffect::ChangeMenu(Rc::new(quit_confirmation))),
         ], 
@@ -418,12 +537,73 @@ pub fn main_menu(_: &State) -> Menu {
     };
 }
 
+/// Choice of the seed and difficulty of a generated level
+pub fn challenge_menu(_: &State) -> Menu {
+    let seed = Rc::new(Cell::new(None));
+
+    let mut elements: Vec<Box<dyn MenuItem>> = vec![
+        label("Type a seed to play a shared puzzle"),
+        Box::new(SeedInput {
+            seed: seed.clone(),
+            last_focused_time: f32::NEG_INFINITY,
+            last_unfocused_time: f32::NEG_INFINITY,
+        }),
+    ];
+
+    for difficulty in generator::DIFFICULTIES {
+        let seed = seed.clone();
+        elements.push(button(&difficulty.to_string(), MenuEffect::ChangeGameMode(Rc::new(move |state| {
+            let seed = seed.get().unwrap_or_else(notan::random::rand::random::<u32>);
+            ingame::get_challenge_state(seed, &difficulty, ScreenSize::zero(), state)
+        }))));
+    }
+
+    elements.push(button("Back", MenuEffect::ChangeMenu(Rc::new(main_menu))));
+
+    return Menu {
+        elements,
+        previous_menu: Some(main_menu),
+    };
+}
+
+/// Tells what the player must do to unlock the campaign, or None if it can be played
+fn missing_requirement(campaign: &crate::parser::Campaign, state: &State) -> Option<String> {
+    for id in campaign.unlock.campaigns.iter() {
+        // Campaigns that don't exist can't be finished, they are ignored
+        let Some(required) = state.campaigns.get(id) else { continue };
+
+        if !required.levels.iter().all(|level| state.progress.get_level(id, level.id).is_some()) {
+            return Some(format!("finish {}", required.name));
+        }
+    }
+
+    let solved_levels: usize = state.progress.levels.values().map(|levels| levels.len()).sum();
+    if solved_levels < campaign.unlock.solved_levels {
+        return Some(format!("solve {} more levels", campaign.unlock.solved_levels - solved_levels));
+    }
+
+    return None;
+}
+
 pub fn campaigns_menu(state: &State) -> Menu {
-    let mut buttons: Vec<Box<dyn MenuItem>> = state.campaigns.iter().map(|(id, campaign)| {
+    let mut campaigns: Vec<_> = state.campaigns.iter().collect();
+    campaigns.sort_by(|(_, a), (_, b)| a.order.cmp(&b.order).then_with(|| a.name.cmp(&b.name)));
+
+    let mut buttons: Vec<Box<dyn MenuItem>> = campaigns.into_iter().map(|(id, campaign)| {
         let id_owned = id.clone();
 
+        let name = if campaign.errors.is_empty() {
+            campaign.name.clone()
+        } else {
+            format!("{} ({} levels skipped)", campaign.name, campaign.errors.len())
+        };
+
+        if let Some(requirement) = missing_requirement(campaign, state) {
+            return label(&format!("{} (locked: {})", name, requirement)) as Box<dyn MenuItem>;
+        }
+
         return button(
-            &campaign.name,
+            &name,
             MenuEffect::ChangeMenu(Rc::new(move |state| level_list(id_owned.clone(), state)))
         ) as Box<dyn MenuItem>;
     }).into_iter().collect();
@@ -437,11 +617,19 @@ pub fn campaigns_menu(state: &State) -> Menu {
 }
 
 pub fn level_list(campaign_id: String, state: &State) -> Menu {
-    let mut levels_buttons: Vec<Box<dyn MenuItem>> = state.campaigns.get(&campaign_id).unwrap().levels.iter().enumerate().map(
+    let campaign = state.campaigns.get(&campaign_id).unwrap();
+
+    // Tell which levels couldn't be read
+    let mut warnings: Vec<Box<dyn MenuItem>> = campaign.errors.iter().map(|e| {
+        label(&format!("Skipped level: {}", e)) as Box<dyn MenuItem>
+    }).collect();
+
+    let mut levels_buttons: Vec<Box<dyn MenuItem>> = campaign.levels.iter().enumerate().map(
         move |(id, level)| Box::new(LevelSelection { 
             campaign_id: campaign_id.clone(), 
             level: level.clone(), 
             id, 
+            progress: state.progress.get_level(&campaign_id, level.id).cloned(),
             last_focused_time: 0.0, 
             last_unfocused_time: 0.0 
         }) as Box<dyn MenuItem>
@@ -449,8 +637,10 @@ pub fn level_list(campaign_id: String, state: &State) -> Menu {
 
     levels_buttons.push(button("Back", MenuEffect::ChangeMenu(Rc::new(main_menu))));
 
+    warnings.append(&mut levels_buttons);
+
     return Menu { 
-        elements: levels_buttons, 
+        elements: warnings, 
         previous_menu: Some(campaigns_menu),
     };
 }
@@ -0,0 +1,206 @@
+
+use std::collections::HashMap;
+use std::fs;
+use crate::proof::{Proof, natural_logic, count_rules, uses_rule};
+use crate::replay::Recording;
+
+
+pub const PROGRESS_FILE: &str = "progress.ron";
+
+/// What the player has done so far, saved next to the settings
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+pub struct Progress {
+    /// Solved levels of each campaign, indexed by the level id. Levels missing here are unsolved.
+    pub levels: HashMap<String, HashMap<usize, LevelProgress>>,
+    /// Daily challenges solved, indexed by the date as YYYY-MM-DD
+    #[serde(default)]
+    pub daily: HashMap<String, DailyResult>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LevelProgress {
+    /// Date of the first solution, as YYYY-MM-DD
+    pub date: String,
+    /// Personal bests, only counting the solutions found without hints
+    pub best_time: Option<f32>,
+    pub fewest_rules: Option<u32>,
+    /// Stays true until the level is solved without RAA
+    pub raa_used: bool,
+    /// Actions of the fastest solution found without hints, and of the last solution
+    #[serde(default)]
+    pub best_run: Option<Recording>,
+    #[serde(default)]
+    pub last_run: Recording,
+}
+
+/// First solution of a daily challenge. Later solutions don't replace it, so that the results of the players can be compared.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DailyResult {
+    pub seed: u32,
+    pub time: f32,
+    pub rule_count: u32,
+    pub raa_used: bool,
+    pub assisted: bool,
+}
+
+/// A finished proof of a level
+pub struct Solution {
+    pub time: f32,
+    pub rule_count: u32,
+    pub raa_used: bool,
+    /// Found with hints
+    pub assisted: bool,
+    pub recording: Recording,
+}
+
+impl Solution {
+    pub fn from_proof(p: &Proof, time: f32, assisted: bool, recording: Recording) -> Solution {
+        return Solution {
+            time,
+            rule_count: count_rules(p),
+            raa_used: uses_rule(p, natural_logic::RAA_RULE),
+            assisted,
+            recording,
+        };
+    }
+}
+
+impl Progress {
+    pub fn get_level(&self, campaign_id: &str, level_id: usize) -> Option<&LevelProgress> {
+        return self.levels.get(campaign_id)?.get(&level_id);
+    }
+
+    pub fn record_solution(&mut self, campaign_id: &str, level_id: usize, solution: &Solution, date: &str) {
+        let level = self.levels.entry(campaign_id.to_string()).or_default().entry(level_id).or_insert_with(|| LevelProgress {
+            date: date.to_string(),
+            best_time: None,
+            fewest_rules: None,
+            raa_used: true,
+            best_run: None,
+            last_run: Vec::new(),
+        });
+
+        level.raa_used &= solution.raa_used;
+
+        if !solution.assisted {
+            if level.best_time.is_none_or(|t| solution.time < t) {
+                level.best_time = Some(solution.time);
+                level.best_run = Some(solution.recording.clone());
+            }
+            level.fewest_rules = Some(level.fewest_rules.map_or(solution.rule_count, |c| u32::min(c, solution.rule_count)));
+        }
+
+        level.last_run = solution.recording.clone();
+    }
+
+    pub fn get_daily(&self, date: &str) -> Option<&DailyResult> {
+        return self.daily.get(date);
+    }
+
+    /// Returns false if the daily challenge of the date was already solved
+    pub fn record_daily(&mut self, date: &str, seed: u32, solution: &Solution) -> bool {
+        if self.daily.contains_key(date) {
+            return false;
+        }
+
+        self.daily.insert(date.to_string(), DailyResult {
+            seed,
+            time: solution.time,
+            rule_count: solution.rule_count,
+            raa_used: solution.raa_used,
+            assisted: solution.assisted,
+        });
+
+        return true;
+    }
+}
+
+
+pub struct LoadError {
+    pub message: String,
+}
+
+pub struct SaveError {
+    pub message: String,
+}
+
+pub fn save_progress(progress: &Progress) -> Result<(), SaveError> {
+    let txt = ron::to_string(progress).map_err(|e| create_save_error(&format!("Couldn't serialize progress: {}", e), PROGRESS_FILE))?;
+    return fs::write(PROGRESS_FILE, txt).map_err(|e| create_save_error(&format!("Couldn't write file: {}", e), PROGRESS_FILE));
+}
+
+pub fn load_progress() -> Result<Progress, LoadError> {
+    match fs::read_to_string(PROGRESS_FILE) {
+        Ok(text) => {
+            return ron::from_str(&text).map_err(|e| create_load_error(&format!("Couldn't parse the file: {}", e), PROGRESS_FILE));
+        },
+        Err(e) => {
+            match e.kind() {
+                std::io::ErrorKind::NotFound => { // Nothing done yet
+                    return Ok(Progress::default());
+                },
+                _ => Err(create_load_error(&format!("Couldn't read file: {}", e), PROGRESS_FILE)),
+            }
+        },
+    }
+}
+
+fn create_save_error(message: &str, file_path: &str) -> SaveError {
+    return SaveError {
+        message: format!("Error while saving progress file {}: {}", file_path, message),
+    };
+}
+
+fn create_load_error(message: &str, file_path: &str) -> LoadError {
+    return LoadError {
+        message: format!("Error while loading progress file {}: {}", file_path, message),
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::Action;
+    use crate::replay::InputEvent;
+
+    #[test]
+    fn personal_bests() {
+        let mut progress = Progress::default();
+        let run = |time| vec![InputEvent { time, action: Action::InsertRule(0), special_mode: false }];
+        let solution = |time, rule_count, raa_used, assisted| Solution { time, rule_count, raa_used, assisted, recording: run(time) };
+
+        progress.record_solution("intro.sq", 3, &solution(20.0, 5, true, true), "2026-10-17");
+        assert_eq!(progress.get_level("intro.sq", 3), Some(&LevelProgress {
+            date: String::from("2026-10-17"), best_time: None, fewest_rules: None, raa_used: true, best_run: None, last_run: run(20.0),
+        }));
+
+        progress.record_solution("intro.sq", 3, &solution(25.0, 6, false, false), "2026-10-18");
+        progress.record_solution("intro.sq", 3, &solution(30.0, 4, true, false), "2026-10-18");
+
+        assert_eq!(progress.get_level("intro.sq", 3), Some(&LevelProgress {
+            date: String::from("2026-10-17"), best_time: Some(25.0), fewest_rules: Some(4), raa_used: false, best_run: Some(run(25.0)), last_run: run(30.0),
+        }));
+        assert_eq!(progress.get_level("intro.sq", 4), None);
+
+        let loaded: Progress = ron::from_str(&ron::to_string(&progress).unwrap()).unwrap();
+        assert_eq!(loaded.get_level("intro.sq", 3), progress.get_level("intro.sq", 3));
+    }
+
+    #[test]
+    fn daily_results() {
+        let mut progress = Progress::default();
+        let solution = |time, rule_count| Solution { time, rule_count, raa_used: false, assisted: false, recording: Vec::new() };
+
+        assert!(progress.record_daily("2026-10-18", 12, &solution(40.0, 9)));
+        assert!(!progress.record_daily("2026-10-18", 12, &solution(10.0, 5)));
+        assert!(progress.record_daily("2026-10-19", 13, &solution(15.0, 6)));
+
+        assert_eq!(progress.get_daily("2026-10-18"), Some(&DailyResult { seed: 12, time: 40.0, rule_count: 9, raa_used: false, assisted: false }));
+        assert_eq!(progress.get_daily("2026-10-20"), None);
+
+        // Progress files written before daily challenges
+        let loaded: Progress = ron::from_str("(levels: {})").unwrap();
+        assert!(loaded.daily.is_empty());
+    }
+}
@@ -4,27 +4,11 @@ use std::collections::HashMap;
 use crate::animation;
 use crate::VerticalAlign;
 use crate::proof::*;
+use crate::proof::layout::*;
 use crate::coord::*;
 use notan::prelude::*;
 use notan::draw::*;
 
-// Screen units
-pub const PROOF_MARGIN: f32 = 200e-3;
-pub const SEQUENT_MARGIN: f32 = 30e-3;
-pub const COMMA_MARGIN: f32 = 10e-3;
-pub const FIELD_WIDTH: f32 = 55e-3;
-pub const FIELD_HEIGHT: f32 = 70e-3;
-pub const FIELD_Y_SHIFT: f32 = 3e-3;
-pub const TEXT_SCALE: f32 = 50.0;
-pub const LINE_HEIGHT: f32 = 120e-3;
-pub const BAR_HEIGHT: f32 = 5e-3;
-pub const PAR_POSITION: f32 = 100e-3;
-pub const OPERATOR_MARGIN: f32 = 10e-3;
-pub const VARIABLE_Y_SHIFT: f32 = -7e-3;
-
-pub const RULE_MARGIN: f32 = 10e-3;
-pub const RULE_TEXT_SCALE: f32 = 0.5; // 1 is normal text
-
 pub const APPEAR_TAU: f32 = 0.05;
 pub const APPEAR_OVERSHOOT: f32 = 1.0;
 pub const APPEAR_RULE_OVERSHOOT: f32 = 2.0;
@@ -32,9 +16,6 @@ pub const FIELD_APPEAR_TAU: f32 = 0.02;
 
 pub const SYMBOLS: &str = "¬→∧∨⊤⊥⊢";
 
-/// Letters used for variables, in order
-pub const VARIABLE_LETTERS: &str = "ABCDEFGHIJK";
-
 pub struct RenderInfo<'a> {
     pub draw: &'a mut Draw,
     pub gfx: &'a mut Graphics,
@@ -52,6 +33,24 @@ pub struct RenderInfo<'a> {
     pub fields_creation_time: &'a mut HashMap<u32, f32>,
 }
 
+impl LayoutInfo for RenderInfo<'_> {
+    fn char_widths(&self) -> &HashMap<char, f32> {
+        self.cached_sizes
+    }
+
+    fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    fn appear_scale(&mut self, creation_time: f32) -> f32 {
+        animation::ease_out_exp_second(self.time - creation_time, APPEAR_TAU, APPEAR_OVERSHOOT)
+    }
+
+    fn field_scale(&mut self, field_id: u32) -> f32 {
+        get_or_create_field_size(field_id, self.fields_creation_time, self.time)
+    }
+}
+
 
 pub fn draw_proof(p: &Proof, bottom_left: ScreenPosition, info: &mut RenderInfo) {
     let branches_width = get_proof_branches_width(p, info);
@@ -91,6 +90,8 @@ pub fn draw_proof(p: &Proof, bottom_left: ScreenPosition, info: &mut RenderInfo)
 
     let bar_color = if p.is_rule_invalid {
         info.theme.seq_invalid
+    } else if p.is_unprovable {
+        info.theme.seq_unprovable
     } else if p.last_focused_time == info.time { 
         info.theme.seq_bar_focused
     } else { 
@@ -110,6 +111,8 @@ pub fn draw_proof(p: &Proof, bottom_left: ScreenPosition, info: &mut RenderInfo)
 
     let rule_color = if p.is_rule_invalid {
         info.theme.seq_invalid
+    } else if p.is_unprovable {
+        info.theme.seq_unprovable
     } else {
         info.theme.seq_text
     };
@@ -159,8 +162,8 @@ pub fn draw_sequent(s: &Sequent, bottom_left: ScreenPosition, squish_x: f32, inf
             pos.x += COMMA_MARGIN * info.scale;
         }
 
-        draw_formula(f, pos, squish_x, info);
-        pos.x += get_formula_width(f, info) * squish_x;
+        draw_formula(f, &s.variables, pos, squish_x, info);
+        pos.x += get_formula_width(f, &s.variables, info) * squish_x;
     }
 
     if s.before.len() > 0 { pos.x += SEQUENT_MARGIN * info.scale * squish_x };
@@ -175,13 +178,13 @@ pub fn draw_sequent(s: &Sequent, bottom_left: ScreenPosition, squish_x: f32, inf
             pos.x += COMMA_MARGIN * info.scale * squish_x;
         }
 
-        draw_formula(f, pos, squish_x, info);
-        pos.x += get_formula_width(f, info) * squish_x;
+        draw_formula(f, &s.variables, pos, squish_x, info);
+        pos.x += get_formula_width(f, &s.variables, info) * squish_x;
     }
 }
 
 
-pub fn draw_formula(f: &Formula, bottom_left: ScreenPosition, squish_x: f32, info: &mut RenderInfo) {
+pub fn draw_formula(f: &Formula, variables: &[String], bottom_left: ScreenPosition, squish_x: f32, info: &mut RenderInfo) {
     match f {
         Formula::Operator(operator) => {
             let arity = get_operator_arity(operator.operator_type);
@@ -202,8 +205,8 @@ pub fn draw_formula(f: &Formula, bottom_left: ScreenPosition, squish_x: f32, inf
                         draw_pos.x += draw_text(&opening_parenthesis, draw_pos, info.text_font, info) * squish_x;
                     }
 
-                    draw_formula(f, draw_pos, squish_x, info);
-                    draw_pos.x += get_formula_width(f, info) * squish_x;
+                    draw_formula(f, variables, draw_pos, squish_x, info);
+                    draw_pos.x += get_formula_width(f, variables, info) * squish_x;
 
                     if need_p {
                         draw_pos.x += draw_text(&closing_parenthesis, draw_pos, info.text_font, info) * squish_x;
@@ -226,8 +229,8 @@ pub fn draw_formula(f: &Formula, bottom_left: ScreenPosition, squish_x: f32, inf
                         draw_pos.x += draw_text(&opening_parenthesis, draw_pos, info.text_font, info) * squish_x;
                     }
 
-                    draw_formula(f, draw_pos, squish_x, info);
-                    draw_pos.x += get_formula_width(f, info) * squish_x;
+                    draw_formula(f, variables, draw_pos, squish_x, info);
+                    draw_pos.x += get_formula_width(f, variables, info) * squish_x;
 
                     if need_p {
                         draw_pos.x += draw_text(&closing_parenthesis, draw_pos, info.text_font, info) * squish_x;
@@ -238,7 +241,7 @@ pub fn draw_formula(f: &Formula, bottom_left: ScreenPosition, squish_x: f32, inf
         },
         Formula::Variable(id) => {
             let actual_pos = ScreenPosition { x: bottom_left.x, y: bottom_left.y + VARIABLE_Y_SHIFT * info.scale };
-            draw_text(&VARIABLE_LETTERS.chars().nth(*id as usize).unwrap().to_string(), actual_pos, info.text_font, info);
+            draw_text(&variable_name(variables, *id), actual_pos, info.text_font, info);
         },
         Formula::NotCompleted(field_info) => {
             let color = if info.editing_formulas && Some(field_info.id) == info.focused_formula_field { 
@@ -271,90 +274,6 @@ pub fn draw_formula(f: &Formula, bottom_left: ScreenPosition, squish_x: f32, inf
     }
 }
 
-
-pub fn get_proof_width(p: &Proof, info: &mut RenderInfo) -> f32 {
-    let x_scale = crate::animation::ease_out_exp_second(info.time - p.creation_time, APPEAR_TAU, APPEAR_OVERSHOOT);
-    return f32::max(get_proof_branches_width(p, info), get_sequent_width(&p.root, info)) * x_scale;
-}
-
-
-fn get_proof_branches_width(p: &Proof, info: &mut RenderInfo) -> f32 {
-    let mut sum = if p.branches.len() > 0 { (p.branches.len() - 1) as f32 * PROOF_MARGIN * info.scale } else { 0.0 };
-
-    for proof in p.branches.iter() {
-        sum += get_proof_width(proof, info);
-    }
-
-    return sum;
-}
-
-pub fn get_proof_root_width(p: &Proof, info: &mut RenderInfo) -> f32 {
-    let x_scale = crate::animation::ease_out_exp_second(info.time - p.creation_time, APPEAR_TAU, APPEAR_OVERSHOOT);
-    return get_sequent_width(&p.root, info) * x_scale;
-}
-
-pub fn get_sequent_width(s: &Sequent, info: &mut RenderInfo) -> f32 {
-    let mut sum = get_character_width('⊢', info);
-
-    if s.before.len() > 0 { sum += SEQUENT_MARGIN * info.scale };
-    if s.after.len() > 0 { sum += SEQUENT_MARGIN * info.scale };
-
-    let comma_size = COMMA_MARGIN + get_character_width(',', info);
-    if s.before.len() > 0 { sum += (s.before.len() as f32 - 1.0) * comma_size };
-    if s.before.len() > 0 { sum += (s.after.len() as f32 - 1.0) * comma_size };
-
-    for f in s.before.iter().chain(s.after.iter()) {
-        sum += get_formula_width(f, info);
-    }
-
-    return sum;
-}
-
-
-pub fn get_formula_width(f: &Formula, info: &mut RenderInfo) -> f32 {
-    match f {
-        Formula::Operator(operator) => {
-            let mut sum = get_character_width(get_operator_symbol(operator.operator_type).chars().next().unwrap(), info);
-            sum += get_operator_arity(operator.operator_type) as f32 * OPERATOR_MARGIN * info.scale;
-
-            let priority = get_operator_priority(operator.operator_type);
-            let parentheses_width = get_character_width('(', info) + get_character_width(')', info) ;
-
-            if operator.arg1.is_some() { 
-                if needs_parentheses(priority, operator.arg1.as_ref().unwrap()) {
-                    sum += parentheses_width;
-                }
-
-                sum += get_formula_width(operator.arg1.as_ref().unwrap(), info);
-            }
-            if operator.arg2.is_some() { 
-                if needs_parentheses(priority, operator.arg2.as_ref().unwrap()) {
-                    sum += parentheses_width;
-                }
-
-                sum += get_formula_width(operator.arg2.as_ref().unwrap(), info);
-            }
-            
-            return sum;
-        },
-        Formula::Variable(id) => {
-            return get_character_width(VARIABLE_LETTERS.chars().nth(*id as usize).unwrap(), info);
-        },
-        Formula::NotCompleted(field_info) => {
-            return FIELD_WIDTH * info.scale * get_or_create_field_size(field_info.id, info.fields_creation_time, info.time);
-        },
-    }
-}
-
-
-pub fn get_character_width(char: char, info: &RenderInfo) -> f32 {    
-    match info.cached_sizes.get(&char) {
-        Some(w) => *w / 1080.0 * 2.0 * info.scale, // Why 1080.0? No one knows...
-        None => panic!("Unknown char width. Add it to SYMBOLS constant!"),
-    }
-}
-
-
 /// Computes the width of the chars
 pub fn compute_char_sizes(text_font: &notan::text::Font, symbol_font: &notan::text::Font) -> HashMap<char, f32> {
     let mut res = HashMap::new();
@@ -382,6 +301,9 @@ pub fn compute_char_sizes(text_font: &notan::text::Font, symbol_font: &notan::te
     for c in 'a'..((b'z' + 1) as char) {
         insert_char(c, text_font, &mut res, &mut calculator)
     }
+    for c in '0'..((b'9' + 1) as char) {
+        insert_char(c, text_font, &mut res, &mut calculator)
+    }
 
     for c in SYMBOLS.chars() {
         insert_char(c, symbol_font, &mut res, &mut calculator)
@@ -395,17 +317,6 @@ pub fn compute_char_sizes(text_font: &notan::text::Font, symbol_font: &notan::te
 }
 
 
-fn needs_parentheses(parent_priority: f32, f: &Formula) -> bool {
-    match f {
-        Formula::Operator(operator) => {
-            get_operator_priority(operator.operator_type) >= parent_priority
-        },
-        Formula::Variable(_) => false,
-        Formula::NotCompleted(_) => false,
-    }
-}
-
-
 fn draw_text(text: &str, position: ScreenPosition, font: &Font, info: &mut RenderInfo) -> f32 {
     draw_text_more_params(text, position, font, 1.0, VerticalAlign::Bottom, info.theme.seq_text, info)
 }
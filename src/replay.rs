@@ -0,0 +1,169 @@
+
+use notan::app::App;
+use crate::action::{self, Action, Bindings};
+
+
+/// An action done by the player while solving a level
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct InputEvent {
+    /// Time since the start of the level
+    pub time: f32,
+    pub action: Action,
+    /// Was the special rule mode key down
+    pub special_mode: bool,
+}
+
+/// Every action that changed the proof, in order
+pub type Recording = Vec<InputEvent>;
+
+/// Actions that change the proof. Only these ones are recorded and replayed.
+pub fn is_recorded(action: Action) -> bool {
+    matches!(action,
+        Action::InsertOperator(_) | Action::InsertVariable(_) | Action::InsertRule(_) |
+        Action::NextField | Action::PreviousField |
+        Action::Undo | Action::Redo | Action::Restart | Action::Hint
+    )
+}
+
+/// Recorded actions pressed during a frame, read from the keyboard or from a replay
+pub struct FrameInput {
+    pub pressed: Vec<Action>,
+    pub special_mode: bool,
+}
+
+impl FrameInput {
+    pub fn from_keyboard(bindings: &Bindings, app: &App) -> FrameInput {
+        return FrameInput {
+            pressed: bindings.iter()
+                .filter(|(action, key)| is_recorded(**action) && app.keyboard.was_pressed(**key))
+                .map(|(action, _)| *action)
+                .collect(),
+            special_mode: action::is_down(Action::SpecialRuleMode, bindings, app),
+        };
+    }
+
+    pub fn was_pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    /// Events to add to the recording. time is the time since the start of the level.
+    pub fn events(&self, time: f32) -> Vec<InputEvent> {
+        return self.pressed.iter().map(|action| InputEvent { time, action: *action, special_mode: self.special_mode }).collect();
+    }
+}
+
+/// A recording being played back, from the start of the level
+pub struct Replay {
+    pub recording: Recording,
+    pub next_event: usize,
+}
+
+impl Replay {
+    pub fn new(recording: Recording) -> Replay {
+        return Replay { recording, next_event: 0 };
+    }
+
+    /// True when every event has been played
+    pub fn is_finished(&self) -> bool {
+        return self.next_event >= self.recording.len();
+    }
+
+    /// Events are given one per frame, so that each one is handled like a single key press.
+    /// time is the time since the start of the level.
+    pub fn next_frame_input(&mut self, time: f32) -> FrameInput {
+        match self.recording.get(self.next_event) {
+            Some(event) if event.time <= time => {
+                self.next_event += 1;
+                return FrameInput { pressed: vec![event.action], special_mode: event.special_mode };
+            },
+            _ => {
+                return FrameInput { pressed: Vec::new(), special_mode: false };
+            },
+        }
+    }
+}
+
+/// Input of the frame: from the replay if there is one, else from the keyboard, which is added to the recording.
+/// The keyboard is read again when the replay is finished or when a key is pressed. The recording then starts with the played events.
+/// replayed stays true until the player presses a key, so that a proof finished by the replay isn't taken for theirs.
+/// time is the time since the start of the level.
+pub fn frame_input(replay: &mut Option<Replay>, replayed: &mut bool, recording: &mut Recording, keyboard: FrameInput, time: f32) -> FrameInput {
+    if !keyboard.pressed.is_empty() {
+        *replayed = false;
+    }
+
+    if let Some(r) = replay {
+        if !r.is_finished() && keyboard.pressed.is_empty() {
+            return r.next_frame_input(time);
+        }
+
+        *recording = r.recording[..r.next_event].to_vec();
+        *replay = None;
+    }
+
+    recording.extend(keyboard.events(time));
+    return keyboard;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(pressed: Vec<Action>) -> FrameInput {
+        FrameInput { pressed, special_mode: false }
+    }
+
+    fn event(time: f32, action: Action) -> InputEvent {
+        InputEvent { time, action, special_mode: false }
+    }
+
+    #[test]
+    fn replay_ends() {
+        let mut replay = Some(Replay::new(vec![event(1.0, Action::InsertRule(2)), event(1.0, Action::NextField)]));
+        let mut replayed = true;
+        let mut recording = Vec::new();
+
+        assert!(frame_input(&mut replay, &mut replayed, &mut recording, keys(vec![]), 0.5).pressed.is_empty());
+        assert_eq!(frame_input(&mut replay, &mut replayed, &mut recording, keys(vec![]), 1.5).pressed, vec![Action::InsertRule(2)]);
+        assert_eq!(frame_input(&mut replay, &mut replayed, &mut recording, keys(vec![]), 1.6).pressed, vec![Action::NextField]);
+        assert!(replay.is_some() && recording.is_empty());
+
+        // The keyboard is used again once every event has been played
+        assert_eq!(frame_input(&mut replay, &mut replayed, &mut recording, keys(vec![Action::Undo]), 2.0).pressed, vec![Action::Undo]);
+        assert!(replay.is_none() && !replayed);
+        assert_eq!(recording, vec![event(1.0, Action::InsertRule(2)), event(1.0, Action::NextField), event(2.0, Action::Undo)]);
+    }
+
+    #[test]
+    fn player_takes_over() {
+        let mut replay = Some(Replay::new(vec![event(1.0, Action::InsertRule(2)), event(3.0, Action::Restart)]));
+        let mut replayed = true;
+        let mut recording = Vec::new();
+
+        assert_eq!(frame_input(&mut replay, &mut replayed, &mut recording, keys(vec![]), 2.0).pressed, vec![Action::InsertRule(2)]);
+        assert_eq!(frame_input(&mut replay, &mut replayed, &mut recording, keys(vec![Action::Redo]), 2.5).pressed, vec![Action::Redo]);
+        assert!(replay.is_none() && !replayed);
+        assert_eq!(recording, vec![event(1.0, Action::InsertRule(2)), event(2.5, Action::Redo)]);
+
+        // The rest of the replay is not played
+        assert!(frame_input(&mut replay, &mut replayed, &mut recording, keys(vec![]), 4.0).pressed.is_empty());
+    }
+
+    #[test]
+    fn finished_replay_is_not_the_player() {
+        let mut replay = Some(Replay::new(vec![event(1.0, Action::InsertRule(2))]));
+        let mut replayed = true;
+        let mut recording = Vec::new();
+
+        assert_eq!(frame_input(&mut replay, &mut replayed, &mut recording, keys(vec![]), 1.0).pressed, vec![Action::InsertRule(2)]);
+
+        // The proof is completed on the next frame, without any key pressed
+        assert!(frame_input(&mut replay, &mut replayed, &mut recording, keys(vec![]), 1.1).pressed.is_empty());
+        assert!(replay.is_none() && replayed);
+        assert_eq!(recording, vec![event(1.0, Action::InsertRule(2))]);
+
+        assert!(frame_input(&mut replay, &mut replayed, &mut recording, keys(vec![]), 1.2).pressed.is_empty());
+        assert!(replayed);
+    }
+}
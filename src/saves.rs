@@ -0,0 +1,93 @@
+
+use std::fs;
+use crate::ingame::UndoState;
+use crate::proof::Sequent;
+
+
+/// Folder with one save slot per level
+pub const SAVES_PATH: &str = "saves";
+
+/// In-progress proof of a level, restored when the level is reopened
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct LevelSave {
+    /// Sequent of the level when it was saved. The save is ignored if the level changed since.
+    pub initial_sequent: Sequent,
+    pub state: UndoState,
+    pub undo_stack: Vec<UndoState>,
+    pub redo_stack: Vec<UndoState>,
+    /// Time already spent on the level
+    pub elapsed_time: f32,
+    pub hints_used: u32,
+    #[serde(default)]
+    pub recording: crate::replay::Recording,
+}
+
+
+pub struct LoadError {
+    pub message: String,
+}
+
+pub struct SaveError {
+    pub message: String,
+}
+
+/// Name of the campaign that can be used in file names.
+/// Campaigns are named after their file in the legacy format, whose extension isn't kept.
+pub fn campaign_file_name(campaign_id: &str) -> String {
+    return campaign_id.strip_suffix(".sq").unwrap_or(campaign_id).chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+}
+
+fn get_save_path(campaign_id: &str, level_id: usize) -> String {
+    return format!("{}/{}_{}.ron", SAVES_PATH, campaign_file_name(campaign_id), level_id + 1);
+}
+
+pub fn save_level(campaign_id: &str, level_id: usize, save: &LevelSave) -> Result<(), SaveError> {
+    let path = get_save_path(campaign_id, level_id);
+
+    let txt = ron::to_string(save).map_err(|e| create_save_error(&format!("Couldn't serialize the proof: {}", e), &path))?;
+    fs::create_dir_all(SAVES_PATH).map_err(|e| create_save_error(&format!("Couldn't create the folder: {}", e), &path))?;
+    return fs::write(&path, txt).map_err(|e| create_save_error(&format!("Couldn't write file: {}", e), &path));
+}
+
+/// Empties the slot of the level. Does nothing if there is no save.
+pub fn delete_level_save(campaign_id: &str, level_id: usize) -> Result<(), SaveError> {
+    let path = get_save_path(campaign_id, level_id);
+
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(create_save_error(&format!("Couldn't delete file: {}", e), &path)),
+    }
+}
+
+/// Returns None if the level has no save
+pub fn load_level(campaign_id: &str, level_id: usize) -> Result<Option<LevelSave>, LoadError> {
+    let path = get_save_path(campaign_id, level_id);
+
+    match fs::read_to_string(&path) {
+        Ok(text) => {
+            let res = ron::from_str(&text).map_err(|e| create_load_error(&format!("Couldn't parse the file: {}", e), &path))?;
+            return Ok(Some(res));
+        },
+        Err(e) => {
+            match e.kind() {
+                std::io::ErrorKind::NotFound => Ok(None),
+                _ => Err(create_load_error(&format!("Couldn't read file: {}", e), &path)),
+            }
+        },
+    }
+}
+
+fn create_save_error(message: &str, file_path: &str) -> SaveError {
+    return SaveError {
+        message: format!("Error while saving proof file {}: {}", file_path, message),
+    };
+}
+
+fn create_load_error(message: &str, file_path: &str) -> LoadError {
+    return LoadError {
+        message: format!("Error while loading proof file {}: {}", file_path, message),
+    };
+}
@@ -21,6 +21,8 @@ pub struct Theme {
     pub seq_bar: Color,
     pub seq_bar_focused: Color,
     pub seq_invalid: Color,
+    #[serde(default = "default_seq_unprovable")]
+    pub seq_unprovable: Color,
     pub seq_field: Color,
     pub seq_field_focused: Color,
 } 
@@ -64,7 +66,13 @@ pub fn save_settings(settings: &Settings) -> Result<(), SaveError> {
 pub fn load_settings() -> Result<Settings, LoadError> {
     match fs::read_to_string(SETTINGS_FILE) {
         Ok(text) => {
-            let res = ron::from_str(&text).map_err(|e| create_load_error(&format!("Couldn't parse the file: {}", e), SETTINGS_FILE))?;
+            let mut res: Settings = ron::from_str(&text).map_err(|e| create_load_error(&format!("Couldn't parse the file: {}", e), SETTINGS_FILE))?;
+
+            // Actions added since the file was saved get their default key
+            for (action, key) in crate::action::get_default_bindings() {
+                res.bindings.entry(action).or_insert(key);
+            }
+
             return Ok(res);
         },
         Err(e) => {
@@ -93,11 +101,17 @@ pub fn get_default_theme() -> Theme {
         seq_bar: Color::from_hex(0xeeeeeeff),
         seq_bar_focused: Color::from_hex(0xffccaaff),
         seq_invalid: Color::from_hex(0xff5555ff),
+        seq_unprovable: Color::from_hex(0xffaa33ff),
         seq_field: Color::from_hex(0x30308050),
         seq_field_focused: Color::from_hex(0xffffdd50),
     };
 } 
 
+/// For settings files saved before this color existed
+fn default_seq_unprovable() -> Color {
+    return get_default_theme().seq_unprovable;
+}
+
 fn create_save_error(message: &str, file_path: &str) -> SaveError {
     return SaveError { 
         message: format!("Error while saving settings file {}: {}", file_path, message), 